@@ -118,8 +118,8 @@ normal_expression // without struct init expression
     ('==' | '!=' | '>' | '<' | '>=' | '<=') normal_expression   // ComparisonExpression
   | normal_expression '&&' normal_expression                    // AndBooleanExpression
   | normal_expression '||' normal_expression                    // OrBooleanExpression
-  | assignment_left_hand ('+=' | '-=' | '*=' | '/=' | '%=' | '&=' | '|=' |
-    '^=' | '<<=' | '>>=') expression                            // CompoundAssignmentExpression
+  | assignment_left_hand ('+=' | '-=' | '*=' | '/=' | '%=' | '**=' | '&=' | '|=' |
+    '^=' | '<<=' | '>>=' | '&&=' | '||=') expression            // CompoundAssignmentExpression
   | assignment_left_hand '=' expression                         // AssignmentExpression
   | normal_expression ('..' | '..=') normal_expression          // RangeExpression 
   ;
@@ -331,6 +331,7 @@ LEFT_ANGLE_EQUAL: '<=';
 RIGHT_ANGLE_EQUAL: '>=';
 LEFT_ARROW: '<-';
 RIGHT_ARROW: '->';
+FAT_ARROW: '=>';
 PLUS_EQUAL: '+=';
 MINUS_EQUAL: '-=';
 STAR_EQUAL: '*=';
@@ -341,6 +342,9 @@ DOUBLE_RIGHT_ANGLE_EQUAL: '>>=';
 AMPERSAND_EQUAL: '&=';
 VERTICAL_EQUAL: '|=';
 CARET_EQUAL: '^=';
+DOUBLE_STAR_EQUAL: '**=';
+DOUBLE_AMPERSAND_EQUAL: '&&=';
+DOUBLE_VERTICAL_EQUAL: '||=';
 DOUBLE_DOTS: '..';
 THREE_DOTS: '...';
 QUESTION: '?';
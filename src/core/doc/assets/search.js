@@ -0,0 +1,30 @@
+// Filters the entries of `window.searchIndex`, which is loaded from `search-index.js`.
+(function () {
+  var input = document.getElementById("search");
+  var results = document.getElementById("search-results");
+  var root = document.body.dataset.root || "";
+  var maxResults = 50;
+
+  input.addEventListener("input", function () {
+    var query = input.value.trim().toLowerCase();
+    results.innerHTML = "";
+    if (!query || !window.searchIndex) {
+      return;
+    }
+    var matched = window.searchIndex.filter(function (entry) {
+      return entry.name.toLowerCase().indexOf(query) !== -1;
+    });
+    matched.slice(0, maxResults).forEach(function (entry) {
+      var link = document.createElement("a");
+      link.href = root + entry.path;
+      var owner = entry.parent ? entry.module + "." + entry.parent : entry.module;
+      link.textContent = owner + "." + entry.name + " (" + entry.kind + ")";
+      var item = document.createElement("li");
+      item.appendChild(link);
+      if (entry.summary) {
+        item.appendChild(document.createTextNode(": " + entry.summary));
+      }
+      results.appendChild(item);
+    });
+  });
+})();
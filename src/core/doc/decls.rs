@@ -0,0 +1,115 @@
+use std::path::PathBuf;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DocFormat {
+  // static pages with a search box, browsable offline
+  Html,
+  // pages for code hosting sites, such as a wiki
+  Markdown,
+}
+impl DocFormat {
+  /// Parses the value of `--format=html|markdown`.
+  pub fn from_arg(value: &str) -> Option<DocFormat> {
+    match value {
+      "html" => Some(DocFormat::Html),
+      "markdown" | "md" => Some(DocFormat::Markdown),
+      _ => None,
+    }
+  }
+
+  pub fn get_extension(&self) -> &'static str {
+    match self {
+      DocFormat::Html => "html",
+      DocFormat::Markdown => "md",
+    }
+  }
+}
+
+/// The kind of an item which has its own page.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DocItemKind {
+  Function,
+  Struct,
+  Enum,
+  Trait,
+}
+impl DocItemKind {
+  /// The keyword of the item, which prefixes the file name of its page, such as `struct.People`.
+  pub fn get_keyword(&self) -> &'static str {
+    match self {
+      DocItemKind::Function => "fn",
+      DocItemKind::Struct => "struct",
+      DocItemKind::Enum => "enum",
+      DocItemKind::Trait => "trait",
+    }
+  }
+
+  pub fn get_title(&self) -> &'static str {
+    match self {
+      DocItemKind::Function => "Function",
+      DocItemKind::Struct => "Struct",
+      DocItemKind::Enum => "Enum",
+      DocItemKind::Trait => "Trait",
+    }
+  }
+}
+
+/// A struct field, an enum variant or a method, documented on the page of its item.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocMember {
+  // `field`, `variant` or `method`, which prefixes the anchor of the member
+  pub kind: &'static str,
+  pub name: String,
+  pub signature: String,
+  pub doc: Option<String>,
+}
+
+/// A public declaration of a module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocItem {
+  pub kind: DocItemKind,
+  pub name: String,
+  /// The declaration without bodies, rendered from the AST.
+  ///
+  /// Example: `pub async fn fetch(url, ...options)`
+  pub signature: String,
+  pub doc: Option<String>,
+  pub members: Vec<DocMember>,
+}
+
+/// An impl block, documented on the page of its struct,
+/// or on the page of its module if the struct isn't in the package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocImpl {
+  /// `None` for inherent impl
+  pub trait_name: Option<String>,
+  pub struct_name: String,
+  /// Example: `impl Walkable for People`
+  pub signature: String,
+  pub doc: Option<String>,
+  pub methods: Vec<DocMember>,
+}
+
+/// A source file of the package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocModule {
+  /// The path from the crate root without extension, such as `["net", "http"]` for
+  /// `net/http.n`, which is also the directory of its pages.
+  pub path: Vec<String>,
+  pub items: Vec<DocItem>,
+  pub impls: Vec<DocImpl>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocPackage {
+  pub name: String,
+  pub version: Option<String>,
+  pub modules: Vec<DocModule>,
+}
+
+/// A generated file, the path is relative to the output directory.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocPage {
+  pub path: PathBuf,
+  pub content: String,
+}
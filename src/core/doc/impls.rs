@@ -0,0 +1,904 @@
+use std::path::PathBuf;
+
+use super::decls::{
+  DocFormat, DocImpl, DocItem, DocItemKind, DocMember, DocModule, DocPackage, DocPage,
+};
+use crate::core::shared::ast::statements::{FunctionDeclaration, TopStatement};
+use crate::core::shared::ast::Identifier;
+use crate::core::shared::diagnostics::escape_json;
+
+const STYLE_CSS: &str = include_str!("assets/style.css");
+const SEARCH_JS: &str = include_str!("assets/search.js");
+
+/// Example: `self, url, ...options`
+fn get_param_list(
+  has_self: bool,
+  params: &[Identifier],
+  rest_param: Option<&Identifier>,
+) -> String {
+  let mut names: Vec<String> = vec![];
+  if has_self {
+    names.push(String::from("self"));
+  }
+  names.extend(params.iter().map(|param| param.name.clone()));
+  if let Some(rest_param) = rest_param {
+    names.push(format!("...{}", rest_param.name));
+  }
+  names.join(", ")
+}
+
+/// Example: `pub async fn fetch(url, ...options)`, the parentheses are omitted without params.
+fn get_function_signature(func_decl: &FunctionDeclaration) -> String {
+  let mut signature = String::new();
+  if func_decl.is_pub() {
+    signature.push_str("pub ");
+  }
+  if func_decl.is_async() {
+    signature.push_str("async ");
+  }
+  signature.push_str("fn ");
+  signature.push_str(&func_decl.name().name);
+  let param_list = get_param_list(false, func_decl.params(), func_decl.rest_param());
+  if !param_list.is_empty() {
+    signature.push_str(&format!("({})", param_list));
+  }
+  signature
+}
+
+/// Example: `async walk(self, distance)`
+fn get_method_signature(
+  is_async: bool,
+  name: &Identifier,
+  has_self: bool,
+  params: &[Identifier],
+  rest_param: Option<&Identifier>,
+) -> String {
+  format!(
+    "{}{}({})",
+    if is_async { "async " } else { "" },
+    name.name,
+    get_param_list(has_self, params, rest_param)
+  )
+}
+
+/// A declaration with its members in braces, one member per line.
+///
+/// Example: `trait Walkable {\n  stand();\n}`
+fn get_block_signature(head: &str, lines: &[String]) -> String {
+  if lines.is_empty() {
+    return format!("{} {{ }}", head);
+  }
+  let body: String = lines.iter().map(|line| format!("  {}\n", line)).collect();
+  format!("{} {{\n{}}}", head, body)
+}
+
+fn get_method_member(signature: String, name: &Identifier, doc: Option<&str>) -> DocMember {
+  DocMember {
+    kind: "method",
+    name: name.name.clone(),
+    signature,
+    doc: doc.map(String::from),
+  }
+}
+
+/// The first line of a doc, shown in lists and search results.
+fn get_summary(doc: &str) -> &str {
+  doc.lines().next().unwrap_or("")
+}
+
+fn escape_html(raw: &str) -> String {
+  let mut escaped = String::with_capacity(raw.len());
+  for c in raw.chars() {
+    match c {
+      '&' => escaped.push_str("&amp;"),
+      '<' => escaped.push_str("&lt;"),
+      '>' => escaped.push_str("&gt;"),
+      '"' => escaped.push_str("&quot;"),
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
+
+impl DocModule {
+  /// Collects the public declarations of a parsed file. Structs, traits and impls
+  /// have no `pub` modifier, so they are always documented.
+  pub fn new(path: Vec<String>, top_statements: &[TopStatement]) -> DocModule {
+    let mut items: Vec<DocItem> = vec![];
+    let mut impls: Vec<DocImpl> = vec![];
+    for top_statement in top_statements.iter() {
+      let doc = top_statement.doc().map(String::from);
+      match top_statement {
+        TopStatement::UseStatement(..) => {}
+        TopStatement::FunctionDeclaration(func_decl) => {
+          if func_decl.is_pub() {
+            items.push(DocItem {
+              kind: DocItemKind::Function,
+              name: func_decl.name().name.clone(),
+              signature: get_function_signature(func_decl),
+              doc,
+              members: vec![],
+            });
+          }
+        }
+        TopStatement::EnumStatement {
+          is_pub,
+          name,
+          variants,
+          ..
+        } => {
+          if !*is_pub {
+            continue;
+          }
+          let members: Vec<DocMember> = variants
+            .iter()
+            .map(|(variant_name, fields, variant_doc)| {
+              let field_names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+              DocMember {
+                kind: "variant",
+                name: variant_name.name.clone(),
+                signature: if field_names.is_empty() {
+                  variant_name.name.clone()
+                } else {
+                  format!("{} {{ {} }}", variant_name.name, field_names.join(", "))
+                },
+                doc: variant_doc.clone(),
+              }
+            })
+            .collect();
+          let lines: Vec<String> = members
+            .iter()
+            .map(|member| format!("{},", member.signature))
+            .collect();
+          items.push(DocItem {
+            kind: DocItemKind::Enum,
+            name: name.name.clone(),
+            signature: get_block_signature(&format!("pub enum {}", name.name), &lines),
+            doc,
+            members,
+          });
+        }
+        TopStatement::StructDeclaration { name, fields, .. } => {
+          let members: Vec<DocMember> = fields
+            .iter()
+            .filter(|field| field.is_pub())
+            .map(|field| DocMember {
+              kind: "field",
+              name: field.name().name.clone(),
+              signature: format!(
+                "pub {}{}",
+                field.name().name,
+                if field.is_optional() { "?" } else { "" }
+              ),
+              doc: field.doc().map(String::from),
+            })
+            .collect();
+          let mut lines: Vec<String> = members
+            .iter()
+            .map(|member| format!("{};", member.signature))
+            .collect();
+          if members.len() < fields.len() {
+            lines.push(String::from("// some fields are private"));
+          }
+          items.push(DocItem {
+            kind: DocItemKind::Struct,
+            name: name.name.clone(),
+            signature: get_block_signature(&format!("struct {}", name.name), &lines),
+            doc,
+            members,
+          });
+        }
+        TopStatement::TraitDeclaration { name, methods, .. } => {
+          let members: Vec<DocMember> = methods
+            .iter()
+            .map(|(method, has_self)| {
+              let signature = get_method_signature(
+                method.is_async(),
+                method.name(),
+                *has_self,
+                method.params(),
+                method.rest_param(),
+              );
+              get_method_member(signature, method.name(), method.doc())
+            })
+            .collect();
+          let lines: Vec<String> = members
+            .iter()
+            .map(|member| format!("{};", member.signature))
+            .collect();
+          items.push(DocItem {
+            kind: DocItemKind::Trait,
+            name: name.name.clone(),
+            signature: get_block_signature(&format!("trait {}", name.name), &lines),
+            doc,
+            members,
+          });
+        }
+        TopStatement::ImplDeclaration {
+          trait_name,
+          struct_name,
+          methods,
+          ..
+        } => {
+          let methods: Vec<DocMember> = methods
+            .iter()
+            .map(|(method, has_self)| {
+              let signature = get_method_signature(
+                method.is_async(),
+                method.name(),
+                *has_self,
+                method.params(),
+                method.rest_param(),
+              );
+              get_method_member(signature, method.name(), method.doc())
+            })
+            .collect();
+          impls.push(DocImpl {
+            trait_name: trait_name
+              .as_ref()
+              .map(|trait_name| trait_name.name.clone()),
+            struct_name: struct_name.name.clone(),
+            signature: match trait_name {
+              Some(trait_name) => format!("impl {} for {}", trait_name.name, struct_name.name),
+              None => format!("impl {}", struct_name.name),
+            },
+            doc,
+            methods,
+          });
+        }
+      }
+    }
+    DocModule { path, items, impls }
+  }
+}
+
+impl DocPackage {
+  /// Example: `nebula_examples::net::http`
+  pub fn get_module_name(&self, module: &DocModule) -> String {
+    let mut segments: Vec<&str> = vec![&self.name];
+    segments.extend(module.path.iter().map(String::as_str));
+    segments.join("::")
+  }
+
+  /// Finds an item by name for a link from the module. The items of the module itself
+  /// are preferred, then an item of other modules if it's the only one with the name.
+  /// Returns: module index, item
+  pub fn find_item(
+    &self,
+    module_index: usize,
+    kind: Option<DocItemKind>,
+    name: &str,
+  ) -> Option<(usize, &DocItem)> {
+    let is_matched =
+      |item: &&DocItem| item.name == name && kind.is_none_or(|kind| item.kind == kind);
+    if let Some(item) = self
+      .modules
+      .get(module_index)?
+      .items
+      .iter()
+      .find(is_matched)
+    {
+      return Some((module_index, item));
+    }
+    let mut found = self
+      .modules
+      .iter()
+      .enumerate()
+      .filter(|(index, _)| *index != module_index)
+      .flat_map(|(index, module)| {
+        module
+          .items
+          .iter()
+          .filter(is_matched)
+          .map(move |item| (index, item))
+      });
+    let first = found.next()?;
+    if found.next().is_some() {
+      // ambiguous, the link is not given
+      return None;
+    }
+    Some(first)
+  }
+}
+
+/// Builds the content of a page, the links are relative to the page,
+/// so the generated directory can be moved or browsed offline.
+struct PageWriter {
+  format: DocFormat,
+  // `../` repeated for the depth of the page, which leads to the output directory
+  root: String,
+  content: String,
+}
+impl PageWriter {
+  fn new(format: DocFormat, page_path: &str) -> PageWriter {
+    PageWriter {
+      format,
+      root: "../".repeat(page_path.matches('/').count()),
+      content: String::new(),
+    }
+  }
+
+  fn text(&self, text: &str) -> String {
+    match self.format {
+      DocFormat::Html => escape_html(text),
+      DocFormat::Markdown => text.to_string(),
+    }
+  }
+
+  fn code(&self, code: &str) -> String {
+    match self.format {
+      DocFormat::Html => format!("<code>{}</code>", escape_html(code)),
+      DocFormat::Markdown => format!("`{}`", code),
+    }
+  }
+
+  fn link_to(&self, fragment: &str, href: &str) -> String {
+    match self.format {
+      DocFormat::Html => format!("<a href=\"{}\">{}</a>", escape_html(href), fragment),
+      DocFormat::Markdown => format!("[{}]({})", fragment, href),
+    }
+  }
+
+  /// A link to the page, whose path is relative to the output directory.
+  fn link(&self, fragment: &str, target: &str) -> String {
+    self.link_to(fragment, &format!("{}{}", self.root, target))
+  }
+
+  fn push_heading(&mut self, level: usize, anchor: Option<&str>, fragment: &str) {
+    match self.format {
+      DocFormat::Html => {
+        let id = anchor
+          .map(|anchor| format!(" id=\"{}\"", escape_html(anchor)))
+          .unwrap_or_default();
+        self
+          .content
+          .push_str(&format!("<h{}{}>{}</h{}>\n", level, id, fragment, level));
+      }
+      DocFormat::Markdown => {
+        self
+          .content
+          .push_str(&format!("{} {}\n\n", "#".repeat(level), fragment));
+      }
+    }
+  }
+
+  fn push_paragraph(&mut self, fragment: &str) {
+    match self.format {
+      DocFormat::Html => self.content.push_str(&format!("<p>{}</p>\n", fragment)),
+      DocFormat::Markdown => self.content.push_str(&format!("{}\n\n", fragment)),
+    }
+  }
+
+  fn push_list(&mut self, fragments: &[String]) {
+    match self.format {
+      DocFormat::Html => {
+        self.content.push_str("<ul>\n");
+        for fragment in fragments.iter() {
+          self.content.push_str(&format!("<li>{}</li>\n", fragment));
+        }
+        self.content.push_str("</ul>\n");
+      }
+      DocFormat::Markdown => {
+        for fragment in fragments.iter() {
+          self.content.push_str(&format!("- {}\n", fragment));
+        }
+        self.content.push('\n');
+      }
+    }
+  }
+
+  fn push_code_block(&mut self, code: &str) {
+    match self.format {
+      DocFormat::Html => self
+        .content
+        .push_str(&format!("<pre><code>{}</code></pre>\n", escape_html(code))),
+      DocFormat::Markdown => self
+        .content
+        .push_str(&format!("```nebula\n{}\n```\n\n", code)),
+    }
+  }
+
+  /// Renders the inline markup of a doc line: code spans, links, and links to items
+  /// such as `[People]` or ``[`People`]``, which are resolved to the path of their pages.
+  fn render_inline(&self, text: &str, resolve: &dyn Fn(&str) -> Option<String>) -> String {
+    let mut rendered = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find(['`', '[']) {
+      rendered.push_str(&self.text(&rest[..index]));
+      rest = &rest[index..];
+      if let Some(code_span) = rest.strip_prefix('`') {
+        // nothing in a code span is rendered
+        let Some(code_end) = code_span.find('`') else {
+          break;
+        };
+        rendered.push_str(&self.code(&code_span[..code_end]));
+        rest = &code_span[code_end + 1..];
+        continue;
+      }
+      let Some(label_end) = rest.find(']') else {
+        break;
+      };
+      let label = &rest[1..label_end];
+      let after_label = &rest[label_end + 1..];
+      if let Some(url_end) = after_label.strip_prefix('(').and_then(|url| url.find(')')) {
+        // an explicit link, such as `[the spec](https://...)`
+        let url = &after_label[1..url_end + 1];
+        rendered.push_str(&self.link_to(&self.render_inline(label, resolve), url));
+        rest = &after_label[url_end + 2..];
+      } else {
+        match resolve(label.trim_matches('`')) {
+          Some(target) => {
+            rendered.push_str(&self.link(&self.render_inline(label, resolve), &target))
+          }
+          None => rendered.push_str(&self.text(&rest[..label_end + 1])),
+        }
+        rest = after_label;
+      }
+    }
+    rendered.push_str(&self.text(rest));
+    rendered
+  }
+
+  fn push_paragraph_lines(&mut self, lines: &mut Vec<String>) {
+    if !lines.is_empty() {
+      self.push_paragraph(&lines.join("\n"));
+      lines.clear();
+    }
+  }
+
+  /// Markdown docs are kept as they are except the links to items, while HTML docs
+  /// are rendered with paragraphs, fenced code blocks and the inline markup.
+  fn push_doc(&mut self, doc: &str, resolve: &dyn Fn(&str) -> Option<String>) {
+    let mut paragraph_lines: Vec<String> = vec![];
+    let mut code_lines: Option<Vec<&str>> = None;
+    for line in doc.lines() {
+      let is_fence = line.trim_start().starts_with("```");
+      if self.format == DocFormat::Markdown {
+        if is_fence {
+          code_lines = if code_lines.is_some() {
+            None
+          } else {
+            Some(vec![])
+          };
+        }
+        if is_fence || code_lines.is_some() {
+          paragraph_lines.push(line.to_string());
+        } else {
+          paragraph_lines.push(self.render_inline(line, resolve));
+        }
+        continue;
+      }
+      if is_fence {
+        match code_lines.take() {
+          Some(lines) => self.push_code_block(&lines.join("\n")),
+          None => {
+            self.push_paragraph_lines(&mut paragraph_lines);
+            code_lines = Some(vec![]);
+          }
+        }
+      } else if let Some(lines) = code_lines.as_mut() {
+        lines.push(line);
+      } else if line.trim().is_empty() {
+        self.push_paragraph_lines(&mut paragraph_lines);
+      } else {
+        paragraph_lines.push(self.render_inline(line, resolve));
+      }
+    }
+    if let Some(lines) = code_lines.filter(|_| self.format == DocFormat::Html) {
+      // the fence is not closed
+      self.push_code_block(&lines.join("\n"));
+    }
+    self.push_paragraph_lines(&mut paragraph_lines);
+  }
+
+  fn finish(self, title: &str, package_name: &str) -> String {
+    match self.format {
+      DocFormat::Markdown => format!("{}\n", self.content.trim_end()),
+      DocFormat::Html => format!(
+        "<!DOCTYPE html>\n\
+        <html lang=\"en\">\n\
+        <head>\n\
+        <meta charset=\"utf-8\">\n\
+        <title>{title}</title>\n\
+        <link rel=\"stylesheet\" href=\"{root}style.css\">\n\
+        </head>\n\
+        <body data-root=\"{root}\">\n\
+        <nav><a href=\"{root}index.html\">{package_name}</a>\
+        <input id=\"search\" type=\"search\" placeholder=\"Search...\" autocomplete=\"off\"></nav>\n\
+        <ul id=\"search-results\"></ul>\n\
+        <main>\n{content}</main>\n\
+        <script src=\"{root}search-index.js\"></script>\n\
+        <script src=\"{root}search.js\"></script>\n\
+        </body>\n\
+        </html>\n",
+        title = escape_html(title),
+        root = self.root,
+        package_name = escape_html(package_name),
+        content = self.content,
+      ),
+    }
+  }
+}
+
+/// Renders the documentation of a package. Every module has an index page and
+/// every item has its own page, under the directory of the module.
+pub struct DocRenderer<'a> {
+  package: &'a DocPackage,
+  format: DocFormat,
+}
+impl<'a> DocRenderer<'a> {
+  pub fn new(package: &'a DocPackage, format: DocFormat) -> DocRenderer<'a> {
+    DocRenderer { package, format }
+  }
+
+  /// Example: `enums/index.html`
+  fn get_module_page(&self, module: &DocModule) -> String {
+    format!(
+      "{}/index.{}",
+      module.path.join("/"),
+      self.format.get_extension()
+    )
+  }
+
+  /// Example: `enums/enum.ExceptionResponse.html`
+  fn get_item_page(&self, module: &DocModule, item: &DocItem) -> String {
+    format!(
+      "{}/{}.{}.{}",
+      module.path.join("/"),
+      item.kind.get_keyword(),
+      item.name,
+      self.format.get_extension()
+    )
+  }
+
+  /// The anchors are only given in HTML pages, Markdown viewers have their own.
+  fn get_member_path(&self, page: &str, anchor: &str) -> String {
+    match self.format {
+      DocFormat::Html => format!("{}#{}", page, anchor),
+      DocFormat::Markdown => page.to_string(),
+    }
+  }
+
+  /// The methods of a trait impl are prefixed by the trait,
+  /// since an inherent impl may have methods with the same names.
+  fn get_member_anchor(trait_name: Option<&str>, member: &DocMember) -> String {
+    match trait_name {
+      Some(trait_name) => format!("{}.{}.{}", trait_name, member.kind, member.name),
+      None => format!("{}.{}", member.kind, member.name),
+    }
+  }
+
+  /// Resolves a link in the docs of the module to the page of the item.
+  fn resolve_link(&self, module_index: usize, name: &str) -> Option<String> {
+    let (found_index, item) = self.package.find_item(module_index, None, name)?;
+    Some(self.get_item_page(&self.package.modules[found_index], item))
+  }
+
+  /// The page which documents the impl: the page of its struct,
+  /// or the page of its module if the struct isn't in the package.
+  fn get_impl_page(&self, module_index: usize, doc_impl: &DocImpl) -> String {
+    match self.package.find_item(
+      module_index,
+      Some(DocItemKind::Struct),
+      &doc_impl.struct_name,
+    ) {
+      Some((struct_index, struct_item)) => {
+        self.get_item_page(&self.package.modules[struct_index], struct_item)
+      }
+      None => self.get_module_page(&self.package.modules[module_index]),
+    }
+  }
+
+  /// Returns: module index, impl
+  fn get_impls_of(&self, kind: DocItemKind, target: &DocItem) -> Vec<(usize, &DocImpl)> {
+    let mut found_impls: Vec<(usize, &DocImpl)> = vec![];
+    for (module_index, module) in self.package.modules.iter().enumerate() {
+      for doc_impl in module.impls.iter() {
+        let name = match kind {
+          DocItemKind::Trait => match &doc_impl.trait_name {
+            Some(trait_name) => trait_name,
+            None => continue,
+          },
+          _ => &doc_impl.struct_name,
+        };
+        let resolved = self.package.find_item(module_index, Some(kind), name);
+        if resolved.is_some_and(|(_, item)| std::ptr::eq(item, target)) {
+          found_impls.push((module_index, doc_impl));
+        }
+      }
+    }
+    found_impls
+  }
+
+  fn push_impl(&self, writer: &mut PageWriter, module_index: usize, doc_impl: &DocImpl) {
+    let resolve = |name: &str| self.resolve_link(module_index, name);
+    writer.push_heading(3, None, &writer.code(&doc_impl.signature));
+    if let Some(trait_name) = &doc_impl.trait_name {
+      if let Some((trait_index, trait_item)) =
+        self
+          .package
+          .find_item(module_index, Some(DocItemKind::Trait), trait_name)
+      {
+        let trait_page = self.get_item_page(&self.package.modules[trait_index], trait_item);
+        let trait_link = writer.link(&writer.code(trait_name), &trait_page);
+        writer.push_paragraph(&format!("Implements {}.", trait_link));
+      }
+    }
+    if let Some(doc) = &doc_impl.doc {
+      writer.push_doc(doc, &resolve);
+    }
+    for method in doc_impl.methods.iter() {
+      let anchor = DocRenderer::get_member_anchor(doc_impl.trait_name.as_deref(), method);
+      writer.push_heading(4, Some(&anchor), &writer.code(&method.signature));
+      if let Some(doc) = &method.doc {
+        writer.push_doc(doc, &resolve);
+      }
+    }
+  }
+
+  fn render_package_page(&self) -> DocPage {
+    let path = format!("index.{}", self.format.get_extension());
+    let mut writer = PageWriter::new(self.format, &path);
+    let title = match &self.package.version {
+      Some(version) => format!("{} {}", self.package.name, version),
+      None => self.package.name.clone(),
+    };
+    writer.push_heading(1, None, &format!("Package {}", writer.code(&title)));
+    let module_links: Vec<String> = self
+      .package
+      .modules
+      .iter()
+      .map(|module| {
+        let module_name = self.package.get_module_name(module);
+        writer.link(&writer.code(&module_name), &self.get_module_page(module))
+      })
+      .collect();
+    writer.push_heading(2, None, "Modules");
+    writer.push_list(&module_links);
+    DocPage {
+      path: PathBuf::from(path),
+      content: writer.finish(&title, &self.package.name),
+    }
+  }
+
+  fn render_module_page(&self, module_index: usize) -> DocPage {
+    let module = &self.package.modules[module_index];
+    let path = self.get_module_page(module);
+    let resolve = |name: &str| self.resolve_link(module_index, name);
+    let mut writer = PageWriter::new(self.format, &path);
+    let module_name = self.package.get_module_name(module);
+    let package_index = format!("index.{}", self.format.get_extension());
+    writer.push_paragraph(&writer.link(&writer.text(&self.package.name), &package_index));
+    writer.push_heading(1, None, &format!("Module {}", writer.code(&module_name)));
+    for kind in [
+      DocItemKind::Struct,
+      DocItemKind::Enum,
+      DocItemKind::Trait,
+      DocItemKind::Function,
+    ] {
+      let entries: Vec<String> = module
+        .items
+        .iter()
+        .filter(|item| item.kind == kind)
+        .map(|item| {
+          let item_link = writer.link(&writer.code(&item.name), &self.get_item_page(module, item));
+          match &item.doc {
+            Some(doc) => format!(
+              "{}: {}",
+              item_link,
+              writer.render_inline(get_summary(doc), &resolve)
+            ),
+            None => item_link,
+          }
+        })
+        .collect();
+      if !entries.is_empty() {
+        writer.push_heading(2, None, &format!("{}s", kind.get_title()));
+        writer.push_list(&entries);
+      }
+    }
+    let unresolved_impls: Vec<&DocImpl> = module
+      .impls
+      .iter()
+      .filter(|doc_impl| {
+        self
+          .package
+          .find_item(
+            module_index,
+            Some(DocItemKind::Struct),
+            &doc_impl.struct_name,
+          )
+          .is_none()
+      })
+      .collect();
+    if !unresolved_impls.is_empty() {
+      writer.push_heading(2, None, "Implementations");
+      for doc_impl in unresolved_impls {
+        self.push_impl(&mut writer, module_index, doc_impl);
+      }
+    }
+    DocPage {
+      path: PathBuf::from(path),
+      content: writer.finish(&module_name, &self.package.name),
+    }
+  }
+
+  fn render_item_page(&self, module_index: usize, item: &DocItem) -> DocPage {
+    let module = &self.package.modules[module_index];
+    let path = self.get_item_page(module, item);
+    let resolve = |name: &str| self.resolve_link(module_index, name);
+    let mut writer = PageWriter::new(self.format, &path);
+    let module_name = self.package.get_module_name(module);
+    writer.push_paragraph(&writer.link(&writer.code(&module_name), &self.get_module_page(module)));
+    writer.push_heading(
+      1,
+      None,
+      &format!("{} {}", item.kind.get_title(), writer.code(&item.name)),
+    );
+    writer.push_code_block(&item.signature);
+    if let Some(doc) = &item.doc {
+      writer.push_doc(doc, &resolve);
+    }
+    let members_title = match item.kind {
+      DocItemKind::Function => None,
+      DocItemKind::Struct => Some("Fields"),
+      DocItemKind::Enum => Some("Variants"),
+      DocItemKind::Trait => Some("Required Methods"),
+    };
+    if let Some(members_title) = members_title.filter(|_| !item.members.is_empty()) {
+      writer.push_heading(2, None, members_title);
+      for member in item.members.iter() {
+        let anchor = DocRenderer::get_member_anchor(None, member);
+        writer.push_heading(3, Some(&anchor), &writer.code(&member.signature));
+        if let Some(doc) = &member.doc {
+          writer.push_doc(doc, &resolve);
+        }
+      }
+    }
+    match item.kind {
+      DocItemKind::Struct => {
+        let (trait_impls, inherent_impls): (Vec<_>, Vec<_>) = self
+          .get_impls_of(DocItemKind::Struct, item)
+          .into_iter()
+          .partition(|(_, doc_impl)| doc_impl.trait_name.is_some());
+        for (title, impls) in [
+          ("Implementations", inherent_impls),
+          ("Trait Implementations", trait_impls),
+        ] {
+          if !impls.is_empty() {
+            writer.push_heading(2, None, title);
+            for (impl_index, doc_impl) in impls {
+              self.push_impl(&mut writer, impl_index, doc_impl);
+            }
+          }
+        }
+      }
+      DocItemKind::Trait => {
+        let implementors: Vec<String> = self
+          .get_impls_of(DocItemKind::Trait, item)
+          .into_iter()
+          .map(|(impl_index, doc_impl)| {
+            writer.link(
+              &writer.code(&doc_impl.struct_name),
+              &self.get_impl_page(impl_index, doc_impl),
+            )
+          })
+          .collect();
+        if !implementors.is_empty() {
+          writer.push_heading(2, None, "Implementors");
+          writer.push_list(&implementors);
+        }
+      }
+      DocItemKind::Function | DocItemKind::Enum => {}
+    }
+    DocPage {
+      path: PathBuf::from(path),
+      content: writer.finish(
+        &format!("{}.{}", module_name, item.name),
+        &self.package.name,
+      ),
+    }
+  }
+
+  /// One JSON object per item and member, such as:
+  /// ```json
+  /// {"name":"walk","kind":"method","module":"nebula_examples::struct_and_trait",
+  ///  "parent":"Walkable","path":"struct_and_trait/trait.Walkable.html#method.walk",
+  ///  "summary":"Walks to somewhere."}
+  /// ```
+  /// The path is relative to the output directory, and the parent is `null` for an item.
+  pub fn render_search_index(&self) -> String {
+    let get_entry = |name: &str,
+                     kind: &str,
+                     module_name: &str,
+                     parent: Option<&str>,
+                     path: &str,
+                     doc: Option<&String>| {
+      format!(
+        "{{\"name\":\"{}\",\"kind\":\"{}\",\"module\":\"{}\",\"parent\":{},\"path\":\"{}\",\"summary\":\"{}\"}}",
+        escape_json(name),
+        kind,
+        escape_json(module_name),
+        parent
+          .map(|parent| format!("\"{}\"", escape_json(parent)))
+          .unwrap_or(String::from("null")),
+        escape_json(path),
+        escape_json(doc.map_or("", |doc| get_summary(doc)))
+      )
+    };
+    let mut entries: Vec<String> = vec![];
+    for (module_index, module) in self.package.modules.iter().enumerate() {
+      let module_name = self.package.get_module_name(module);
+      for item in module.items.iter() {
+        let page = self.get_item_page(module, item);
+        let kind = item.kind.get_keyword();
+        entries.push(get_entry(
+          &item.name,
+          kind,
+          &module_name,
+          None,
+          &page,
+          item.doc.as_ref(),
+        ));
+        for member in item.members.iter() {
+          let anchor = DocRenderer::get_member_anchor(None, member);
+          entries.push(get_entry(
+            &member.name,
+            member.kind,
+            &module_name,
+            Some(&item.name),
+            &self.get_member_path(&page, &anchor),
+            member.doc.as_ref(),
+          ));
+        }
+      }
+      for doc_impl in module.impls.iter() {
+        let page = self.get_impl_page(module_index, doc_impl);
+        for method in doc_impl.methods.iter() {
+          let anchor = DocRenderer::get_member_anchor(doc_impl.trait_name.as_deref(), method);
+          entries.push(get_entry(
+            &method.name,
+            method.kind,
+            &module_name,
+            Some(&doc_impl.struct_name),
+            &self.get_member_path(&page, &anchor),
+            method.doc.as_ref(),
+          ));
+        }
+      }
+    }
+    format!("[\n{}\n]\n", entries.join(",\n"))
+  }
+
+  /// All pages of the package and the search index, and the assets of HTML pages.
+  pub fn render_pages(&self) -> Vec<DocPage> {
+    let mut pages: Vec<DocPage> = vec![self.render_package_page()];
+    for (module_index, module) in self.package.modules.iter().enumerate() {
+      pages.push(self.render_module_page(module_index));
+      for item in module.items.iter() {
+        pages.push(self.render_item_page(module_index, item));
+      }
+    }
+    let search_index = self.render_search_index();
+    if self.format == DocFormat::Html {
+      // a script instead of fetching the JSON, which is blocked for local files
+      pages.push(DocPage {
+        path: PathBuf::from("search-index.js"),
+        content: format!("window.searchIndex = {};\n", search_index.trim_end()),
+      });
+      pages.push(DocPage {
+        path: PathBuf::from("search.js"),
+        content: SEARCH_JS.to_string(),
+      });
+      pages.push(DocPage {
+        path: PathBuf::from("style.css"),
+        content: STYLE_CSS.to_string(),
+      });
+    }
+    pages.push(DocPage {
+      path: PathBuf::from("search-index.json"),
+      content: search_index,
+    });
+    pages
+  }
+}
@@ -0,0 +1,3 @@
+mod test_collect_items;
+mod test_generate_docs;
+mod test_render_pages;
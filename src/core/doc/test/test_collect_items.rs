@@ -0,0 +1,121 @@
+#[test]
+fn test_collect_public_items() {
+  use crate::core::{
+    doc::decls::{DocItemKind, DocModule},
+    parser::impls::Parser,
+  };
+  let source = "use std::io;
+/// Fetches the url.
+pub async fn fetch(url, ...options) { }
+fn private_helper(a) { }
+pub fn main { }
+enum Hidden { A }
+pub enum Status {
+  /// Found nothing.
+  NotFound,
+  Moved { location, permanent },
+}
+struct People {
+  /// The full name.
+  pub name;
+  pub desc?;
+  age;
+}
+trait Walkable {
+  stand();
+  async walk(self, distance);
+}
+impl Walkable for People {
+  stand() { }
+  async walk(self, distance) { }
+}";
+  let (top_statements, errors) = Parser::new(source).parse_program();
+  assert!(errors.is_empty());
+  let module = DocModule::new(vec![String::from("net")], &top_statements);
+  let items: Vec<(DocItemKind, &str, &str)> = module
+    .items
+    .iter()
+    .map(|item| (item.kind, item.name.as_str(), item.signature.as_str()))
+    .collect();
+  assert_eq!(
+    items,
+    vec![
+      (
+        DocItemKind::Function,
+        "fetch",
+        "pub async fn fetch(url, ...options)"
+      ),
+      (DocItemKind::Function, "main", "pub fn main"),
+      (
+        DocItemKind::Enum,
+        "Status",
+        "pub enum Status {\n  NotFound,\n  Moved { location, permanent },\n}"
+      ),
+      (
+        DocItemKind::Struct,
+        "People",
+        "struct People {\n  pub name;\n  pub desc?;\n  // some fields are private\n}"
+      ),
+      (
+        DocItemKind::Trait,
+        "Walkable",
+        "trait Walkable {\n  stand();\n  async walk(self, distance);\n}"
+      ),
+    ]
+  );
+  assert_eq!(module.items[0].doc.as_deref(), Some("Fetches the url."));
+  assert_eq!(
+    module.items[2].members[0].doc.as_deref(),
+    Some("Found nothing.")
+  );
+  let field_names: Vec<&str> = module.items[3]
+    .members
+    .iter()
+    .map(|member| member.name.as_str())
+    .collect();
+  assert_eq!(field_names, vec!["name", "desc"]);
+
+  assert_eq!(module.impls.len(), 1);
+  assert_eq!(module.impls[0].signature, "impl Walkable for People");
+  assert_eq!(module.impls[0].trait_name.as_deref(), Some("Walkable"));
+  assert_eq!(
+    module.impls[0].methods[1].signature,
+    "async walk(self, distance)"
+  );
+}
+
+#[test]
+fn test_find_items_across_modules() {
+  use crate::core::{
+    doc::decls::{DocItemKind, DocModule, DocPackage},
+    parser::impls::Parser,
+  };
+  let get_module = |path: &str, source: &str| {
+    let (top_statements, errors) = Parser::new(source).parse_program();
+    assert!(errors.is_empty());
+    DocModule::new(vec![path.to_string()], &top_statements)
+  };
+  let package = DocPackage {
+    name: String::from("shop"),
+    version: None,
+    modules: vec![
+      get_module("models", "struct Order { } struct Item { }"),
+      get_module("views", "struct Item { } pub fn render(order) { }"),
+      get_module("admin", "pub fn render(order) { }"),
+    ],
+  };
+  assert_eq!(package.get_module_name(&package.modules[1]), "shop::views");
+  // the item of the module itself is preferred
+  let (module_index, _) = package.find_item(1, None, "Item").unwrap();
+  assert_eq!(module_index, 1);
+  // the only one in other modules
+  let (module_index, _) = package
+    .find_item(2, Some(DocItemKind::Struct), "Order")
+    .unwrap();
+  assert_eq!(module_index, 0);
+  assert!(package
+    .find_item(2, Some(DocItemKind::Function), "Order")
+    .is_none());
+  // ambiguous between `models` and `views`
+  assert!(package.find_item(2, None, "Item").is_none());
+}
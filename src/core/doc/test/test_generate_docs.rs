@@ -0,0 +1,31 @@
+#[test]
+fn test_generate_docs_of_package() {
+  use crate::core::{
+    doc::decls::DocFormat,
+    entry::{generate_docs, DocOptions},
+  };
+  use crate::utils::log::ColorChoice;
+  let out_dir = std::env::temp_dir().join(format!("nebula_doc_test_{}", std::process::id()));
+  let options = DocOptions {
+    path: format!("{}/src/core/doc/test/package", env!("CARGO_MANIFEST_DIR")),
+    format: DocFormat::Markdown,
+    out_dir: Some(out_dir.display().to_string()),
+    color: ColorChoice::Never,
+  };
+  let result = generate_docs(&options);
+  let read_page = |path: &str| std::fs::read_to_string(out_dir.join(path)).unwrap();
+  assert!(result.is_ok(), "{:?}", result);
+  assert!(read_page("index.md").contains("- [`town::net::walk`](net/walk/index.md)"));
+  // the impl in `net/walk.n` is documented on the page of the struct in `people.n`
+  let people_page = read_page("people/struct.People.md");
+  assert!(
+    people_page.contains("A person, who can walk by [Walkable](../net/walk/trait.Walkable.md).")
+  );
+  assert!(people_page.contains("### `impl Walkable for People`"));
+  assert!(people_page.contains("#### `greet(self)`\n\nSays hello."));
+  assert!(read_page("net/walk/fn.walk_all.md").contains("pub async fn walk_all(place, ...people)"));
+  // private functions are not documented
+  assert!(!out_dir.join("people/fn.helper.md").exists());
+  assert!(read_page("search-index.json").contains("\"name\":\"walk_all\""));
+  std::fs::remove_dir_all(&out_dir).unwrap();
+}
@@ -0,0 +1,135 @@
+/// A package of two modules, the impl is in another module than its struct.
+#[cfg(test)]
+fn get_test_package() -> crate::core::doc::decls::DocPackage {
+  use crate::core::{
+    doc::decls::{DocModule, DocPackage},
+    parser::impls::Parser,
+  };
+  let people_source = "/// A person, see [`Walkable`].
+///
+/// ```
+/// var john = People { name = \"<John>\" };
+/// ```
+struct People {
+  /// The full name.
+  pub name;
+}";
+  let walk_source = "/// Things that can walk.
+trait Walkable {
+  stand();
+}
+impl Walkable for People {
+  /// Stands still.
+  stand() { }
+}";
+  let mut modules: Vec<DocModule> = vec![];
+  for (path, source) in [("people", people_source), ("walk", walk_source)] {
+    let (top_statements, errors) = Parser::new(source).parse_program();
+    assert!(errors.is_empty());
+    modules.push(DocModule::new(vec![path.to_string()], &top_statements));
+  }
+  DocPackage {
+    name: String::from("town"),
+    version: Some(String::from("0.1.0")),
+    modules,
+  }
+}
+
+#[test]
+fn test_render_markdown_pages() {
+  use crate::core::doc::{decls::DocFormat, impls::DocRenderer};
+  let package = get_test_package();
+  let pages = DocRenderer::new(&package, DocFormat::Markdown).render_pages();
+  let paths: Vec<String> = pages
+    .iter()
+    .map(|page| page.path.display().to_string())
+    .collect();
+  assert_eq!(
+    paths,
+    vec![
+      "index.md",
+      "people/index.md",
+      "people/struct.People.md",
+      "walk/index.md",
+      "walk/trait.Walkable.md",
+      "search-index.json",
+    ]
+  );
+  assert_eq!(
+    pages[2].content,
+    "[`town::people`](../people/index.md)
+
+# Struct `People`
+
+```nebula
+struct People {
+  pub name;
+}
+```
+
+A person, see [`Walkable`](../walk/trait.Walkable.md).
+
+```
+var john = People { name = \"<John>\" };
+```
+
+## Fields
+
+### `pub name`
+
+The full name.
+
+## Trait Implementations
+
+### `impl Walkable for People`
+
+Implements [`Walkable`](../walk/trait.Walkable.md).
+
+#### `stand()`
+
+Stands still.
+"
+  );
+  // the implementors are linked to the page of the struct
+  assert!(pages[4]
+    .content
+    .ends_with("## Implementors\n\n- [`People`](../people/struct.People.md)\n"));
+}
+
+#[test]
+fn test_render_html_pages_and_search_index() {
+  use crate::core::doc::{decls::DocFormat, impls::DocRenderer};
+  let package = get_test_package();
+  let renderer = DocRenderer::new(&package, DocFormat::Html);
+  let pages = renderer.render_pages();
+  let people_page = pages
+    .iter()
+    .find(|page| page.path.ends_with("struct.People.html"))
+    .unwrap();
+  for expected in [
+    "<body data-root=\"../\">",
+    "<p>A person, see <a href=\"../walk/trait.Walkable.html\"><code>Walkable</code></a>.</p>",
+    "<pre><code>var john = People { name = &quot;&lt;John&gt;&quot; };</code></pre>",
+    "<h3 id=\"field.name\"><code>pub name</code></h3>",
+    "<h4 id=\"Walkable.method.stand\"><code>stand()</code></h4>",
+  ] {
+    assert!(people_page.content.contains(expected), "{}", expected);
+  }
+  assert!(pages
+    .iter()
+    .any(|page| page.path.ends_with("search-index.js")));
+
+  let search_index = renderer.render_search_index();
+  let entries: Vec<&str> = search_index.lines().collect();
+  assert_eq!(entries.len(), 7);
+  assert_eq!(
+    entries[1],
+    "{\"name\":\"People\",\"kind\":\"struct\",\"module\":\"town::people\",\"parent\":null,\
+    \"path\":\"people/struct.People.html\",\"summary\":\"A person, see [`Walkable`].\"},"
+  );
+  assert_eq!(
+    entries[5],
+    "{\"name\":\"stand\",\"kind\":\"method\",\"module\":\"town::walk\",\"parent\":\"People\",\
+    \"path\":\"people/struct.People.html#Walkable.method.stand\",\"summary\":\"Stands still.\"}"
+  );
+}
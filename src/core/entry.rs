@@ -1,64 +1,482 @@
+use crate::core::doc::decls::{DocFormat, DocModule, DocPackage};
+use crate::core::doc::impls::DocRenderer;
+use crate::core::lint::decls::{Lint, LintConfig, LintLevel};
+use crate::core::lint::impls::Linter;
+use crate::core::parser::impls::Parser;
+use crate::core::shared::compile_errors::get_explanation;
+use crate::core::shared::diagnostics::{Diagnostic, Severity};
+use crate::core::shared::nebula_interal_err;
+use crate::core::shared::source_map::SourceMap;
+use crate::core::shared::span::FileId;
+use crate::core::universe::{
+  find_manifest, find_source_files, parse_manifest, DEFAULT_CRATE_ROOT, LINTS_TABLE, MANIFEST_NAME,
+};
+use crate::utils::log::ColorChoice;
 use crate::utils::{log, shared::return_and_print_err};
 use std::env;
-use std::fs::File;
-use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 
 pub fn get_env_args() -> Result<Vec<String>, String> {
   let args: Vec<String> = env::args().collect();
   if args.len() <= 1 {
     return_and_print_err("must give a target file".to_string())
   } else {
-    log::info(&format!("Running args: {:?}", args));
     Ok(args)
   }
 }
 
-pub fn read_from_source(canonicalized: std::io::Result<PathBuf>) -> Result<String, String> {
-  if let Ok(absolute_path) = canonicalized {
-    if let Some(file_path) = absolute_path.to_str() {
-      let file_path = file_path.to_string();
-      log::info(&format!("Opening file: {}", file_path));
-      match File::open(&file_path) {
-        Err(why) => {
-          return_and_print_err(format!("couldn't open {} ({})", file_path, why.to_string()))
+/// Loads the target file into the source map, the name is shown in diagnostics.
+/// The error is reported by the caller.
+pub fn read_from_source(
+  source_map: &mut SourceMap,
+  file_name: &str,
+  canonicalized: std::io::Result<PathBuf>,
+) -> Result<FileId, String> {
+  let Ok(absolute_path) = canonicalized else {
+    return Err(String::from("file path canonicalized failed"));
+  };
+  if absolute_path.to_str().is_none() {
+    return Err(String::from(
+      "target file path is not a valid UTF-8 sequence",
+    ));
+  }
+  log::info(&format!("Opening file: {}", absolute_path.display()));
+  source_map.load_file(file_name, &absolute_path)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorFormat {
+  // rustc-style diagnostics with source snippets
+  Human,
+  // one JSON object per line, for editors and CI
+  Json,
+}
+impl ErrorFormat {
+  /// Parses the value of `--error-format=human|json`.
+  pub fn from_arg(value: &str) -> Option<ErrorFormat> {
+    match value {
+      "human" => Some(ErrorFormat::Human),
+      "json" => Some(ErrorFormat::Json),
+      _ => None,
+    }
+  }
+}
+
+pub struct CompileOptions {
+  pub file_path: String,
+  pub color: ColorChoice,
+  pub error_format: ErrorFormat,
+  /// Levels given by `-A`, `-W` and `-D`, applied in order.
+  pub lint_levels: Vec<(Vec<Lint>, LintLevel)>,
+}
+
+pub struct DocOptions {
+  /// The directory of the package, or any file in it.
+  pub path: String,
+  pub format: DocFormat,
+  /// Defaults to `target/doc` in the directory of the package.
+  pub out_dir: Option<String>,
+  pub color: ColorChoice,
+}
+
+pub enum Command {
+  Compile(CompileOptions),
+  // prints the explanation of an error code
+  Explain(String),
+  // generates the documentation of a package
+  Doc(DocOptions),
+}
+
+/// Options of the subcommand `doc`: `[<path>] [--format=html|markdown] [--out-dir=<dir>]
+/// [--color=auto|always|never]`, the path defaults to the current directory.
+fn parse_doc_command(args: &[String]) -> Result<Command, String> {
+  let mut path: Option<String> = None;
+  let mut format = DocFormat::Html;
+  let mut out_dir: Option<String> = None;
+  let mut color = ColorChoice::Auto;
+  for arg in args.iter() {
+    if let Some(value) = arg.strip_prefix("--format=") {
+      match DocFormat::from_arg(value) {
+        Some(doc_format) => format = doc_format,
+        None => {
+          return return_and_print_err(format!(
+            "invalid value '{}' for '--format', expected one of: html, markdown",
+            value
+          ))
+        }
+      }
+    } else if let Some(value) = arg.strip_prefix("--out-dir=") {
+      out_dir = Some(value.to_string());
+    } else if let Some(value) = arg.strip_prefix("--color=") {
+      match ColorChoice::from_arg(value) {
+        Some(choice) => color = choice,
+        None => {
+          return return_and_print_err(format!(
+            "invalid value '{}' for '--color', expected one of: auto, always, never",
+            value
+          ))
+        }
+      }
+    } else if arg.starts_with('-') {
+      return return_and_print_err(format!("unknown option '{}'", arg));
+    } else if path.is_none() {
+      path = Some(arg.clone());
+    } else {
+      return return_and_print_err(format!("unexpected argument '{}'", arg));
+    }
+  }
+  Ok(Command::Doc(DocOptions {
+    path: path.unwrap_or(String::from(".")),
+    format,
+    out_dir,
+    color,
+  }))
+}
+
+/// Options: `<file> [--color=auto|always|never] [--error-format=human|json]
+/// [-A|-W|-D <lint>]...`, the target file is required, or `--explain <code>` alone,
+/// or the subcommand `doc` with its own options.
+pub fn parse_command(args: &[String]) -> Result<Command, String> {
+  if args.get(1).is_some_and(|arg| arg == "doc") {
+    return parse_doc_command(&args[2..]);
+  }
+  let mut file_path: Option<String> = None;
+  let mut color = ColorChoice::Auto;
+  let mut error_format = ErrorFormat::Human;
+  let mut lint_levels: Vec<(Vec<Lint>, LintLevel)> = vec![];
+  // the first one is the executable itself
+  let mut args_iter = args.iter().skip(1);
+  while let Some(arg) = args_iter.next() {
+    if arg == "--explain" {
+      return match args_iter.next() {
+        Some(code) => Ok(Command::Explain(code.to_uppercase())),
+        None => return_and_print_err("'--explain' requires an error code".to_string()),
+      };
+    } else if let Some(code) = arg.strip_prefix("--explain=") {
+      return Ok(Command::Explain(code.to_uppercase()));
+    } else if let Some(value) = arg.strip_prefix("--error-format=") {
+      match ErrorFormat::from_arg(value) {
+        Some(format) => error_format = format,
+        None => {
+          return return_and_print_err(format!(
+            "invalid value '{}' for '--error-format', expected one of: human, json",
+            value
+          ))
         }
-        Ok(mut file) => {
-          let mut content = String::new();
-          match file.read_to_string(&mut content) {
-            Err(why) => {
-              return_and_print_err(format!("couldn't read {} ({})", file_path, why.to_string()))
-            }
-            Ok(_) => Ok(content),
-          }
+      }
+    } else if let Some(value) = arg.strip_prefix("--color=") {
+      match ColorChoice::from_arg(value) {
+        Some(choice) => color = choice,
+        None => {
+          return return_and_print_err(format!(
+            "invalid value '{}' for '--color', expected one of: auto, always, never",
+            value
+          ))
         }
       }
+    } else if let Some(level) = arg.get(..2).and_then(LintLevel::from_flag) {
+      // both `-W unused_variables` and `-Wunused_variables` are accepted
+      let lint_name = match &arg[2..] {
+        "" => match args_iter.next() {
+          Some(lint_name) => lint_name.as_str(),
+          None => return return_and_print_err(format!("'{}' requires a lint name", arg)),
+        },
+        lint_name => lint_name,
+      };
+      match Lint::resolve_name(lint_name) {
+        Some(lints) => lint_levels.push((lints, level)),
+        None => return return_and_print_err(format!("unknown lint '{}'", lint_name)),
+      }
+    } else if arg.starts_with('-') {
+      return return_and_print_err(format!("unknown option '{}'", arg));
+    } else if file_path.is_none() {
+      file_path = Some(arg.clone());
     } else {
-      return_and_print_err(String::from(
-        "target file path is not a valid UTF-8 sequence",
-      ))
+      return return_and_print_err(format!("unexpected argument '{}'", arg));
     }
-  } else {
-    return_and_print_err(String::from("file path canonicalized failed"))
+  }
+  match file_path {
+    Some(file_path) => Ok(Command::Compile(CompileOptions {
+      file_path,
+      color,
+      error_format,
+      lint_levels,
+    })),
+    None => return_and_print_err("must give a target file".to_string()),
   }
 }
 
-pub fn compile_entry_file(_content: String) {
+/// Prints the long-form explanation of the error code,
+/// returns whether the code is known.
+pub fn explain_error_code(code: &str) -> bool {
+  match get_explanation(code) {
+    Some(explanation) => {
+      println!("{}", explanation);
+      true
+    }
+    None => {
+      log::error(&format!(
+        "no extended information for error code '{}'",
+        code
+      ));
+      false
+    }
+  }
+}
+
+/// Loads the lint levels: the defaults, then the `[lints]` table of `universe.toml`
+/// which is found from the entry file, then the command line flags.
+pub fn load_lint_config(
+  entry_path: &Path,
+  lint_levels: &[(Vec<Lint>, LintLevel)],
+) -> Result<LintConfig, String> {
+  let mut lint_config = LintConfig::default();
+  if let Some(manifest_path) = find_manifest(entry_path) {
+    log::info(&format!("Using manifest: {}", manifest_path.display()));
+    let content = std::fs::read_to_string(&manifest_path)
+      .map_err(|why| format!("couldn't read {} ({})", manifest_path.display(), why))?;
+    if let Some(lints_table) = parse_manifest(&content)?.get(LINTS_TABLE) {
+      lint_config.apply_table(lints_table)?;
+    }
+  }
+  for (lints, level) in lint_levels {
+    lint_config.set_level(lints, *level);
+  }
+  Ok(lint_config)
+}
+
+/// Compiles the entry file in the source map and reports the errors and lints in the given
+/// format, returns whether it's compiled without any error (including the denied lints).
+pub fn compile_entry_file(
+  source_map: &SourceMap,
+  file_id: FileId,
+  error_format: ErrorFormat,
+  lint_config: &LintConfig,
+) -> bool {
   log::info(&format!("Nebula Compiler {}", "v0.1"));
-  // TODO: implement compilation
+  let Some(entry_file) = source_map.get_file(file_id) else {
+    log::error(&nebula_interal_err("the entry file is not loaded"));
+    return false;
+  };
+  let mut parser = Parser::new_in_file(entry_file.get_source(), file_id);
+  let (top_statements, errors) = parser.parse_program();
+  let mut diagnostics: Vec<Diagnostic> = errors
+    .iter()
+    .map(|error| Diagnostic::from_compile_error(error, file_id))
+    .collect();
+  // lints are meaningless on a broken AST
+  if errors.is_empty() {
+    let reports = Linter::new().lint_program(&top_statements);
+    // like rustc, the note about the lint level is only given on its first report
+    let mut noted_lints: Vec<Lint> = vec![];
+    for report in reports.iter() {
+      if let Some(mut diagnostic) = report.to_diagnostic(lint_config.get_level(report.lint)) {
+        if noted_lints.contains(&report.lint) {
+          diagnostic.notes.clear();
+        } else {
+          noted_lints.push(report.lint);
+        }
+        diagnostics.push(diagnostic);
+      }
+    }
+  }
+  for diagnostic in diagnostics.iter() {
+    match error_format {
+      ErrorFormat::Human => println!("{}\n", diagnostic.render(source_map)),
+      ErrorFormat::Json => println!("{}", diagnostic.to_json(source_map)),
+    }
+  }
+  if error_format == ErrorFormat::Human && !errors.is_empty() {
+    let mut codes: Vec<&str> = errors.iter().map(|error| error.get_code()).collect();
+    codes.sort();
+    codes.dedup();
+    println!(
+      "Some errors have detailed explanations: {}.\nFor more information about an error, try `nebula --explain {}`.\n",
+      codes.join(", "),
+      codes[0]
+    );
+  }
+  let count_by_severity = |severity: Severity| {
+    diagnostics
+      .iter()
+      .filter(|diagnostic| diagnostic.severity == severity)
+      .count()
+  };
+  let errors_count = count_by_severity(Severity::Error);
+  log::info(&format!(
+    "Parsed {} top statements with {} errors and {} warnings",
+    top_statements.len(),
+    errors_count,
+    count_by_severity(Severity::Warning)
+  ));
+  // TODO: more compilation stages...
+  errors_count == 0
 }
 
-pub fn run() {
-  if let Ok(args) = get_env_args() {
-    // get_env_args ensures that index(1) is not None.
-    let arg_file_path = args.get(1).unwrap();
-    let target_path = Path::new(".").join(&arg_file_path);
-    match read_from_source(target_path.canonicalize()) {
-      Err(why) => log::error(&why),
-      Ok(content) => {
-        compile_entry_file(content)
-        // TODO: more...
+/// Documents the package which contains the path: every source file under its crate root
+/// is parsed, and the pages are written to the output directory.
+/// It fails if any file has compile errors, which are reported as diagnostics.
+pub fn generate_docs(options: &DocOptions) -> Result<(), String> {
+  let target_path = Path::new(".")
+    .join(&options.path)
+    .canonicalize()
+    .map_err(|why| format!("couldn't find {} ({})", options.path, why))?;
+  // the manifest is searched from the parent of the path, so a directory starts with itself
+  let search_from = if target_path.is_dir() {
+    target_path.join(MANIFEST_NAME)
+  } else {
+    target_path
+  };
+  let Some(manifest_path) = find_manifest(&search_from) else {
+    return Err(format!(
+      "couldn't find {} for {}",
+      MANIFEST_NAME, options.path
+    ));
+  };
+  log::info(&format!("Using manifest: {}", manifest_path.display()));
+  let content = std::fs::read_to_string(&manifest_path)
+    .map_err(|why| format!("couldn't read {} ({})", manifest_path.display(), why))?;
+  let tables = parse_manifest(&content)?;
+  let package_table = tables.get("package");
+  let get_package_entry = |key: &str| package_table.and_then(|table| table.get(key)).cloned();
+  let package_dir = manifest_path.parent().unwrap_or(Path::new("."));
+  let package_name = get_package_entry("name")
+    .or_else(|| {
+      package_dir
+        .file_name()
+        .map(|dir_name| dir_name.to_string_lossy().to_string())
+    })
+    .unwrap_or(String::from("package"));
+  let crate_root =
+    package_dir.join(get_package_entry("crateRoot").unwrap_or(String::from(DEFAULT_CRATE_ROOT)));
+
+  let mut source_map = SourceMap::new();
+  let mut modules: Vec<DocModule> = vec![];
+  let mut errors_count = 0;
+  for source_path in find_source_files(&crate_root)? {
+    // the name in diagnostics is relative to the package, such as `src/main.n`
+    let file_name = source_path
+      .strip_prefix(package_dir)
+      .unwrap_or(&source_path);
+    let file_id = source_map.load_file(&file_name.display().to_string(), &source_path)?;
+    let Some(source_file) = source_map.get_file(file_id) else {
+      return Err(nebula_interal_err("the source file is not loaded"));
+    };
+    let (top_statements, errors) =
+      Parser::new_in_file(source_file.get_source(), file_id).parse_program();
+    for error in errors.iter() {
+      let diagnostic = Diagnostic::from_compile_error(error, file_id);
+      println!("{}\n", diagnostic.render(&source_map));
+    }
+    errors_count += errors.len();
+    let module_path: Vec<String> = source_path
+      .strip_prefix(&crate_root)
+      .unwrap_or(&source_path)
+      .with_extension("")
+      .iter()
+      .map(|segment| segment.to_string_lossy().to_string())
+      .collect();
+    modules.push(DocModule::new(module_path, &top_statements));
+  }
+  if errors_count > 0 {
+    return Err(format!(
+      "couldn't document `{}` due to {} previous errors",
+      package_name, errors_count
+    ));
+  }
+
+  let package = DocPackage {
+    name: package_name,
+    version: get_package_entry("version"),
+    modules,
+  };
+  let out_dir = match &options.out_dir {
+    Some(out_dir) => PathBuf::from(out_dir),
+    None => package_dir.join("target").join("doc"),
+  };
+  for page in DocRenderer::new(&package, options.format).render_pages() {
+    let page_path = out_dir.join(&page.path);
+    if let Some(page_dir) = page_path.parent() {
+      std::fs::create_dir_all(page_dir)
+        .map_err(|why| format!("couldn't create {} ({})", page_dir.display(), why))?;
+    }
+    std::fs::write(&page_path, page.content)
+      .map_err(|why| format!("couldn't write {} ({})", page_path.display(), why))?;
+  }
+  log::info(&format!(
+    "Documented {} modules of `{}` to {}",
+    package.modules.len(),
+    package.name,
+    out_dir
+      .join(format!("index.{}", options.format.get_extension()))
+      .display()
+  ));
+  Ok(())
+}
+
+pub fn run() -> ExitCode {
+  let Ok(args) = get_env_args() else {
+    return ExitCode::FAILURE;
+  };
+  // keeps stdout as pure JSON lines, even for the errors of the arguments
+  let is_json = args
+    .iter()
+    .filter_map(|arg| arg.strip_prefix("--error-format="))
+    .next_back()
+    .is_some_and(|value| value == "json");
+  log::set_error_to_stderr(is_json);
+  let options = match parse_command(&args) {
+    Ok(Command::Compile(options)) => options,
+    Ok(Command::Explain(code)) => {
+      return if explain_error_code(&code) {
+        ExitCode::SUCCESS
+      } else {
+        ExitCode::FAILURE
+      };
+    }
+    Ok(Command::Doc(options)) => {
+      log::set_color_choice(options.color);
+      return match generate_docs(&options) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(why) => {
+          log::error(&why);
+          ExitCode::FAILURE
+        }
+      };
+    }
+    Err(_) => return ExitCode::FAILURE,
+  };
+  log::set_color_choice(options.color);
+  // keeps the output as pure JSON lines
+  log::set_info_enabled(options.error_format == ErrorFormat::Human);
+  log::info(&format!("Running args: {:?}", args));
+
+  let target_path = Path::new(".").join(&options.file_path);
+  let canonicalized = target_path.canonicalize();
+  let lint_config = match &canonicalized {
+    Ok(absolute_path) => match load_lint_config(absolute_path, &options.lint_levels) {
+      Ok(lint_config) => lint_config,
+      Err(why) => {
+        log::error(&why);
+        return ExitCode::FAILURE;
+      }
+    },
+    Err(_) => LintConfig::default(),
+  };
+  let mut source_map = SourceMap::new();
+  match read_from_source(&mut source_map, &options.file_path, canonicalized) {
+    Err(why) => {
+      log::error(&why);
+      ExitCode::FAILURE
+    }
+    Ok(file_id) => {
+      if compile_entry_file(&source_map, file_id, options.error_format, &lint_config) {
+        ExitCode::SUCCESS
+      } else {
+        ExitCode::FAILURE
       }
+      // TODO: more...
     }
   }
 }
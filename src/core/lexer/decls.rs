@@ -17,6 +17,8 @@ pub enum TokenType {
   // * Flow control:
   If,
   Else,
+  Loop,
+  While,
   For,
   Each,
   In,
@@ -134,6 +136,8 @@ pub enum TokenType {
   LeftArrow,
   // ->
   RightArrow,
+  // =>
+  FatArrow,
   // +=
   PlusEqual,
   // -=
@@ -154,6 +158,12 @@ pub enum TokenType {
   VerticalEqual,
   // ^=
   CaretEqual,
+  // **=
+  DoubleStarEqual,
+  // &&=
+  DoubleAmpersandEqual,
+  // ||=
+  DoubleVerticalEqual,
   // ..
   DoubleDots,
   // ...
@@ -172,6 +182,21 @@ pub enum TokenType {
   // ----- Identifier symbol:
   Identifier,
 
+  // ----- Documentation:
+  // `/// ...` or `/** ... */`, the raw is the text without the comment markers.
+  // Collected aside for the parser in normal mode, emitted in lossless mode.
+  DocComment,
+
+  // ----- Trivia, only emitted in lossless mode:
+  // a run of whitespaces and line breaks
+  Whitespace,
+  // `// ...`, without the line break
+  LineComment,
+  // `/* ... */`
+  BlockComment,
+  // a character which can't start any token, or the text of an invalid literal
+  Unknown,
+
   // EOF
   EOF,
 }
@@ -180,18 +205,27 @@ pub enum TokenType {
 pub struct Token {
   pub raw: String,
   pub kind: TokenType,
-  pub offset: usize,
+  // byte offsets of the token in source, the span is `start..end`
+  pub start: usize,
+  pub end: usize,
   pub line: usize,
   pub col: usize,
 }
 
 pub struct Lexer<'a> {
+  pub contents: &'a str,
+  // emits trivia and unknown characters as tokens instead of skipping them,
+  // so the tokens cover the source byte for byte
+  pub is_lossless: bool,
+
   // Human readable position in file
   pub cur_line: usize,
   pub cur_col: usize,
 
-  // offset cursor of character moving
+  // offset cursor of character moving, counted in bytes
   pub offset_cursor: usize,
+  // byte offset where the token being lexed starts
+  pub token_start: usize,
 
   pub chars: Peekable<Chars<'a>>,
   pub pair_balance: HashMap<PairPunctuation, PairPuncEntry>,
@@ -201,6 +235,8 @@ pub struct Lexer<'a> {
 
   // collecting errors, don't interrupt lexing process
   pub errors: Vec<CompileError>,
+  // doc comments in source order, the parser attaches them to declarations
+  pub doc_comments: Vec<Token>,
 }
 
 pub enum NumberRadix {
@@ -228,7 +264,7 @@ impl PairPunctuation {
     match self {
       PairPunctuation::Parenthesis => ")".to_string(),
       PairPunctuation::Brace => "}".to_string(),
-      PairPunctuation::Bracket => "[".to_string(),
+      PairPunctuation::Bracket => "]".to_string(),
     }
   }
 }
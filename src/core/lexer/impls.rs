@@ -12,7 +12,8 @@ impl<'a> Lexer<'a> {
     Token {
       raw,
       kind: token_type,
-      offset: self.offset_cursor,
+      start: self.token_start,
+      end: self.offset_cursor,
       line: self.cur_line,
       col: self.cur_col,
     }
@@ -22,6 +23,8 @@ impl<'a> Lexer<'a> {
     Position {
       line: self.cur_line,
       col: self.cur_col,
+      start: self.token_start,
+      end: self.offset_cursor,
     }
   }
 
@@ -35,13 +38,18 @@ impl<'a> Lexer<'a> {
         } else {
           self.cur_col += 1;
         }
-        self.offset_cursor += 1;
+        self.offset_cursor += c.len_utf8();
         Some(c)
       }
       None => None,
     }
   }
 
+  /// The source text of the token being lexed.
+  fn get_token_text(&self) -> String {
+    self.contents[self.token_start..self.offset_cursor].to_string()
+  }
+
   /// move over \t and spaces and update column information
   fn skip_whitespaces(&mut self) {
     while let Some(c) = self.chars.peek() {
@@ -77,6 +85,7 @@ impl<'a> Lexer<'a> {
       self.errors.push(CompileError::ImbalancedPair {
         punc_str: kind.get_right(),
         pos: self.get_current_pos(),
+        open_pos: self.get_last_unclosed_pos(),
       });
       return None;
     }
@@ -86,6 +95,7 @@ impl<'a> Lexer<'a> {
       self.errors.push(CompileError::ImbalancedPair {
         punc_str: kind.get_right(),
         pos: self.get_current_pos(),
+        open_pos: self.get_last_unclosed_pos(),
       });
       // Don't return None here, because we still want to give the token.
       // Because we don't want to break the whole lexing process's consistency.
@@ -100,6 +110,16 @@ impl<'a> Lexer<'a> {
     Some((token_type, kind.get_right()))
   }
 
+  /// The latest open punctuation of any kind which has not been closed yet.
+  fn get_last_unclosed_pos(&self) -> Option<Position> {
+    self
+      .pair_balance
+      .values()
+      .filter_map(|entry| entry.positions.last())
+      .max_by_key(|pos| pos.start)
+      .copied()
+  }
+
   fn multiple_chars_lexing(
     &mut self,
     first: (TokenType, String),
@@ -134,6 +154,16 @@ impl<'a> Lexer<'a> {
     }
   }
 
+  /// look ahead one more character than `match_next_char` without consuming anything
+  fn match_char_after_next(&self, c: char) -> bool {
+    let mut look_ahead = self.chars.clone();
+    look_ahead.next();
+    match look_ahead.peek() {
+      Some(&after_next) => after_next == c,
+      None => false,
+    }
+  }
+
   fn lexing_punctuation(&mut self, c: char) -> Option<(TokenType, String)> {
     match c {
       ';' => Some((TokenType::Semi, String::from(";"))),
@@ -148,6 +178,16 @@ impl<'a> Lexer<'a> {
     }
   }
 
+  /// A doc comment is a token in lossless mode, otherwise it's collected for the parser.
+  fn output_doc_comment(&mut self, doc_text: String) -> Option<Token> {
+    let doc_token = self.create_token(TokenType::DocComment, doc_text);
+    if self.is_lossless {
+      return Some(doc_token);
+    }
+    self.doc_comments.push(doc_token);
+    None
+  }
+
   fn lexing_slash_more(&mut self) -> Option<Token> {
     let single_slash = Some(self.create_token(TokenType::Slash, String::from("/")));
     if let Some(next_char) = self.chars.peek() {
@@ -159,6 +199,20 @@ impl<'a> Lexer<'a> {
         '/' => {
           // skip for line comments
           self.consume_char(); // eat the second slash character
+
+          // `////` is a normal comment
+          let is_doc = self.match_next_char('/') && !self.match_char_after_next('/');
+          if is_doc || self.is_lossless {
+            // the line break is left to the following whitespace token
+            while self.chars.peek().is_some_and(|c| *c != '\n') {
+              self.consume_char();
+            }
+            if is_doc {
+              let doc_text = get_line_doc_text(&self.get_token_text());
+              return self.output_doc_comment(doc_text);
+            }
+            return Some(self.create_token(TokenType::LineComment, self.get_token_text()));
+          }
           while let Some(following_char) = self.consume_char() {
             if following_char == '\n' {
               break;
@@ -169,16 +223,33 @@ impl<'a> Lexer<'a> {
         '*' => {
           // skip block comment, won't support nested style: /* ... /* ... */ ... */
           self.consume_char(); // eat the star character
+          let opening_pos = self.get_current_pos();
+
+          // `/***` and `/**/` are normal comments
+          let is_doc = self.match_next_char('*')
+            && !self.match_char_after_next('*')
+            && !self.match_char_after_next('/');
+          let mut is_closed = false;
           while let Some(following_char) = self.consume_char() {
-            if following_char == '*' {
-              if let Some(after_star) = self.chars.peek() {
-                if *after_star == '/' {
-                  self.consume_char(); // eat '*/'
-                  break;
-                }
-              }
+            if following_char == '*' && self.match_next_char('/') {
+              self.consume_char(); // eat '*/'
+              is_closed = true;
+              break;
             }
           }
+          if !is_closed {
+            // the rest of the file has been swallowed by the comment
+            self
+              .errors
+              .push(CompileError::UnclosedBlockComment { pos: opening_pos });
+          }
+          if is_doc {
+            let doc_text = get_block_doc_text(&self.get_token_text());
+            return self.output_doc_comment(doc_text);
+          }
+          if self.is_lossless {
+            return Some(self.create_token(TokenType::BlockComment, self.get_token_text()));
+          }
           None
         }
         _ => single_slash,
@@ -221,39 +292,35 @@ impl<'a> Lexer<'a> {
     }
   }
 
+  /// A malformed number such as `0x` is reported, but still given as a token of its kind,
+  /// so the parser takes it as an operand and won't report a missing expression there.
   fn lexing_number_by_radix(&mut self, radix: u32, prefix: &'a str) -> Option<Token> {
     let mut digits_collect = Vec::<char>::new();
-    while let Some(moved_next_char) = self.consume_char() {
-      if moved_next_char.is_digit(radix) {
-        digits_collect.push(moved_next_char);
+    while let Some(&next_char) = self.chars.peek() {
+      if next_char.is_digit(radix) {
+        digits_collect.push(next_char);
+        self.consume_char();
       } else {
+        // do not eat the character after digits, it may be a punctuation like ';'
         break;
       }
     }
+    let (token_type, radix_string) = match radix {
+      16 => (TokenType::HexadecimalInteger, "hexadecimal"),
+      8 => (TokenType::OctalInteger, "octal"),
+      2 => (TokenType::BinaryInteger, "binary"),
+      _ => return None,
+    };
     if digits_collect.len() == 0 {
-      let radix_string = match radix {
-        16 => String::from(" hexadecimal "),
-        8 => String::from(" octal "),
-        2 => String::from(" binary "),
-        _ => return None,
-      };
       self.errors.push(CompileError::InvalidFormatNumber {
-        numeric_type: radix_string,
+        numeric_type: String::from(radix_string),
         pos: self.get_current_pos(),
       });
-      None
-    } else {
-      let token_type = match radix {
-        16 => TokenType::HexadecimalInteger,
-        8 => TokenType::OctalInteger,
-        2 => TokenType::BinaryInteger,
-        _ => return None,
-      };
-      Some(self.create_token(
-        token_type,
-        format!("{}{}", prefix, String::from_iter(digits_collect)),
-      ))
     }
+    Some(self.create_token(
+      token_type,
+      format!("{}{}", prefix, String::from_iter(digits_collect)),
+    ))
   }
 
   fn output_special_decimal_result(
@@ -266,19 +333,18 @@ impl<'a> Lexer<'a> {
       // length must be greater than 1 because already contains a symbol ('.' or 'e')
       self.errors.push(CompileError::InvalidFormatNumber {
         numeric_type: match numeric_type {
-          TokenType::Exponent => String::from(" exponent "),
-          TokenType::Float => String::from(" float "),
+          TokenType::Exponent => String::from("exponent"),
+          TokenType::Float => String::from("float"),
           _ => return None,
         },
         pos: self.get_current_pos(),
       });
-      None
-    } else {
-      Some(self.create_token(
-        numeric_type,
-        format!("{}{}", String::from_iter(front), String::from_iter(after)),
-      ))
     }
+    // the malformed number is still given as a token, like in `lexing_number_by_radix`
+    Some(self.create_token(
+      numeric_type,
+      format!("{}{}", String::from_iter(front), String::from_iter(after)),
+    ))
   }
 
   fn lexing_decimal(&mut self, start_digit: char) -> Option<Token> {
@@ -298,7 +364,11 @@ impl<'a> Lexer<'a> {
         }
         self.consume_char();
       } else if next_char == '.' {
-        if !is_exponent {
+        if is_float || self.match_char_after_next('.') {
+          // a second dot can't be a part of number, such as: `1..5`, `1.5..=3`
+          // so considered as the start of range operators, do not eat the '.'
+          break;
+        } else if !is_exponent {
           is_float = true;
           fractional_collect.push(next_char); // collect the '.' character
           self.consume_char(); // eat the '.' character
@@ -377,23 +447,24 @@ impl<'a> Lexer<'a> {
       if c == '\\' {
         // escape character format: t/n/r/\ are available
         self.consume_char(); // eat the backslash
-        if let Some(&char_after_backslash) = self.chars.peek() {
-          if char_after_backslash == '\'' {
+        if self.match_next_char('\'') {
+          self.consume_char();
+          if self.match_next_char('\'') {
             self.consume_char();
             return Some(self.create_token(TokenType::Char, String::from("'")));
-          } else if let Some(escape_char) = self.lexing_escape_char() {
+          }
+        } else if let Some(escape_char) = self.lexing_escape_char() {
+          self.consume_char();
+          if self.match_next_char('\'') {
             self.consume_char();
-            if self.match_next_char('\'') {
-              self.consume_char();
-              return Some(self.create_token(TokenType::Char, String::from(escape_char)));
-            } else {
-              self.errors.push(CompileError::UnclosedCharLiteral {
-                pos: self.get_current_pos(),
-              })
-            }
+            return Some(self.create_token(TokenType::Char, String::from(escape_char)));
           }
         }
+        self.errors.push(CompileError::UnclosedCharLiteral {
+          pos: self.get_current_pos(),
+        });
       } else if c == '\'' {
+        self.consume_char(); // eat the close quote character
         self.errors.push(CompileError::InvalidEmptyChar {
           pos: self.get_current_pos(),
         });
@@ -403,14 +474,44 @@ impl<'a> Lexer<'a> {
         if self.match_next_char('\'') {
           self.consume_char();
           return Some(self.create_token(TokenType::Char, got_char.to_string()));
-        } else {
-          self.errors.push(CompileError::UnclosedCharLiteral {
-            pos: self.get_current_pos(),
-          })
         }
+        self.errors.push(CompileError::UnclosedCharLiteral {
+          pos: self.get_current_pos(),
+        });
+      }
+    } else {
+      self.errors.push(CompileError::UnclosedCharLiteral {
+        pos: self.get_current_pos(),
+      });
+    }
+    self.skip_invalid_char_rest();
+    // the invalid literal is still given as a char, so the parser won't report it again
+    let char_text = self.get_token_text();
+    let raw = char_text
+      .strip_prefix('\'')
+      .map(|text| text.strip_suffix('\'').unwrap_or(text))
+      .unwrap_or(&char_text)
+      .to_string();
+    Some(self.create_token(TokenType::Char, raw))
+  }
+
+  /// Skips the rest of an invalid char literal through its closing quote on the same line,
+  /// otherwise nothing is skipped and the following text is lexed as usual.
+  fn skip_invalid_char_rest(&mut self) {
+    let token_text = self.get_token_text();
+    if token_text.len() > 1 && token_text.ends_with('\'') {
+      return; // closed already, such as `''`
+    }
+    let rest_of_line = self.contents[self.offset_cursor..]
+      .split('\n')
+      .next()
+      .unwrap_or("");
+    if let Some(closing) = rest_of_line.find('\'') {
+      let closing_end = self.offset_cursor + closing + 1;
+      while self.offset_cursor < closing_end {
+        self.consume_char();
       }
     }
-    None
   }
 
   fn lexing_string(&mut self) -> Option<Token> {
@@ -440,8 +541,33 @@ impl<'a> Lexer<'a> {
   }
 
   pub fn peek_next_token(&mut self) -> Option<Token> {
-    self.skip_whitespaces();
+    if !self.is_lossless {
+      return self.lexing_next_token();
+    }
+    let start = self.offset_cursor;
+    match self.lexing_next_token() {
+      Some(token) => Some(token),
+      // keeps the text of an invalid literal, whose error has been collected
+      None if self.offset_cursor > start => {
+        self.token_start = start;
+        Some(self.create_token(TokenType::Unknown, self.get_token_text()))
+      }
+      None => None,
+    }
+  }
+
+  fn lexing_next_token(&mut self) -> Option<Token> {
+    if self.is_lossless {
+      if self.chars.peek().is_some_and(|c| c.is_whitespace()) {
+        self.token_start = self.offset_cursor;
+        self.skip_whitespaces();
+        return Some(self.create_token(TokenType::Whitespace, self.get_token_text()));
+      }
+    } else {
+      self.skip_whitespaces();
+    }
     while let Some(c) = self.consume_char() {
+      self.token_start = self.offset_cursor - c.len_utf8();
       // punctuations are all single-character
       if let Some(punc_tuple) = self.lexing_punctuation(c) {
         let (punc_type, punc_raw) = punc_tuple;
@@ -507,7 +633,8 @@ impl<'a> Lexer<'a> {
           return self.multiple_chars_lexing(
             (TokenType::Equal, String::from("=")),
             hashmap! {
-                '=' => (TokenType::DoubleEqual, String::from("=="), None)
+                '=' => (TokenType::DoubleEqual, String::from("=="), None),
+                '>' => (TokenType::FatArrow, String::from("=>"), None)
             },
           )
         }
@@ -533,7 +660,11 @@ impl<'a> Lexer<'a> {
             (TokenType::Star, String::from("*")),
             hashmap! {
                 '=' => (TokenType::StarEqual, String::from("*="), None),
-                '*' => (TokenType::DoubleStar, String::from("**"), None)
+                '*' => (
+                    TokenType::DoubleStar,
+                    String::from("**"),
+                    Some(vec![('=', TokenType::DoubleStarEqual, String::from("**="))])
+                )
             },
           )
         }
@@ -591,7 +722,11 @@ impl<'a> Lexer<'a> {
             (TokenType::Ampersand, String::from("&")),
             hashmap! {
                 '=' => (TokenType::AmpersandEqual, String::from("&="), None),
-                '&' => (TokenType::DoubleAmpersand, String::from("&&"), None)
+                '&' => (
+                    TokenType::DoubleAmpersand,
+                    String::from("&&"),
+                    Some(vec![('=', TokenType::DoubleAmpersandEqual, String::from("&&="))])
+                )
             },
           )
         }
@@ -600,7 +735,11 @@ impl<'a> Lexer<'a> {
             (TokenType::Vertical, String::from("|")),
             hashmap! {
                 '=' => (TokenType::VerticalEqual, String::from("|="), None),
-                '|' => (TokenType::DoubleVertical, String::from("||"), None)
+                '|' => (
+                    TokenType::DoubleVertical,
+                    String::from("||"),
+                    Some(vec![('=', TokenType::DoubleVerticalEqual, String::from("||="))])
+                )
             },
           )
         }
@@ -617,17 +756,26 @@ impl<'a> Lexer<'a> {
           )
         }
 
-        _ => continue, // accumulating characters (may be identifier)
+        _ => {
+          if self.is_lossless {
+            return Some(self.create_token(TokenType::Unknown, c.to_string()));
+          }
+          continue; // skips the unknown character
+        }
       };
     }
+    self.token_start = self.offset_cursor;
     Some(self.create_token(TokenType::EOF, String::from("\0")))
   }
 
   pub fn new(contents: &'a str) -> Lexer<'a> {
     Self {
+      contents,
+      is_lossless: false,
       cur_line: 1,
       cur_col: 1,
       offset_cursor: 0,
+      token_start: 0,
       chars: contents.chars().peekable(),
       pair_balance: HashMap::new(),
       reserved_words_map: RefCell::new(hashmap! {
@@ -636,6 +784,8 @@ impl<'a> Lexer<'a> {
           "as" => TokenType::As,
           "if" => TokenType::If,
           "else" => TokenType::Else,
+          "loop" => TokenType::Loop,
+          "while" => TokenType::While,
           "for" => TokenType::For,
           "each" => TokenType::Each,
           "in" => TokenType::In,
@@ -661,28 +811,90 @@ impl<'a> Lexer<'a> {
           "Self" => TokenType::_Self_
       }),
       errors: Vec::<CompileError>::new(),
+      doc_comments: vec![],
     }
   }
 
+  /// The tokens include trivia, and the texts of all tokens make up the contents exactly.
+  pub fn new_lossless(contents: &'a str) -> Lexer<'a> {
+    Lexer {
+      is_lossless: true,
+      ..Lexer::new(contents)
+    }
+  }
+
+  /// Lexes all tokens until the EOF, which is not included.
   pub fn peek_all_tokens(&mut self) -> Vec<Token> {
-    let mut tokens = Vec::new();
-    while let Some(token) = self.peek_next_token() {
-      tokens.push(token);
-    }
-
-    // Check if there are any unclosed open punctuataion
-    for (kind, entry) in self.pair_balance.iter() {
-      if entry.count > 0 {
-        for pos in entry.positions.iter() {
-          self.errors.push(CompileError::ImbalancedPair {
-            punc_str: kind.get_left(),
-            pos: *pos,
-          });
+    let tokens: Vec<Token> = self.by_ref().collect();
+    self.collect_unclosed_pair_errors();
+    tokens
+  }
+
+  /// Check if there are any unclosed open punctuataion,
+  /// should only be called after reaching the EOF.
+  pub fn collect_unclosed_pair_errors(&mut self) {
+    // in a fixed order, so the errors are reported in the same order every time
+    for kind in [
+      PairPunctuation::Parenthesis,
+      PairPunctuation::Brace,
+      PairPunctuation::Bracket,
+    ] {
+      if let Some(entry) = self.pair_balance.get(&kind) {
+        if entry.count > 0 {
+          for pos in entry.positions.iter() {
+            self.errors.push(CompileError::ImbalancedPair {
+              punc_str: kind.get_left(),
+              pos: *pos,
+              open_pos: None,
+            });
+          }
         }
       }
     }
+  }
 
-    tokens
+  /// Count of the open punctuations which have been lexed but not closed yet.
+  pub fn get_unclosed_pair_count(&self, kind: PairPunctuation) -> i32 {
+    match self.pair_balance.get(&kind) {
+      Some(entry) => entry.count,
+      None => 0,
+    }
+  }
+}
+
+/// The text of a `///` comment, without the slashes and the space after them.
+fn get_line_doc_text(comment: &str) -> String {
+  let text = comment.strip_prefix("///").unwrap_or(comment);
+  let text = text.strip_prefix(' ').unwrap_or(text);
+  text.strip_suffix('\r').unwrap_or(text).to_string()
+}
+
+/// The text of a `/** */` comment, the leading `*` of every line is removed,
+/// such as the lines of
+/// ```text
+/// /**
+///  * Adds two numbers.
+///  */
+/// ```
+fn get_block_doc_text(comment: &str) -> String {
+  let text = comment.strip_prefix("/**").unwrap_or(comment);
+  let text = text.strip_suffix("*/").unwrap_or(text);
+  let lines: Vec<&str> = text
+    .lines()
+    .map(|line| {
+      let line = line.trim();
+      match line.strip_prefix('*') {
+        Some(rest) => rest.strip_prefix(' ').unwrap_or(rest),
+        None => line,
+      }
+    })
+    .collect();
+  // the blank lines after `/**` and before `*/`
+  let first = lines.iter().position(|line| !line.is_empty());
+  let last = lines.iter().rposition(|line| !line.is_empty());
+  match (first, last) {
+    (Some(first), Some(last)) => lines[first..=last].join("\n"),
+    _ => String::new(),
   }
 }
 
@@ -1,4 +1,6 @@
+mod test_doc_comments;
 mod test_identifiers;
 mod test_numeric;
 mod test_punc_and_op;
 mod test_string;
+mod test_trivia;
@@ -0,0 +1,51 @@
+#[test]
+fn test_collect_doc_comments() {
+  use crate::core::lexer::decls::{Lexer, TokenType};
+  let source = "/// Adds two numbers.\r\n///\n////  not a doc\n/**\n * Returns `a + b`.\n *\n *   indented\n */\n/***/ /**/ /** single */ fn";
+  let mut lexer = Lexer::new(source);
+  let got_types: Vec<TokenType> = lexer.by_ref().map(|token| token.kind).collect();
+  assert_eq!(got_types, vec![TokenType::Fn]);
+  let got_docs: Vec<&str> = lexer
+    .doc_comments
+    .iter()
+    .map(|doc_token| doc_token.raw.as_str())
+    .collect();
+  assert_eq!(
+    got_docs,
+    vec![
+      "Adds two numbers.",
+      "",
+      "Returns `a + b`.\n\n  indented",
+      "single"
+    ]
+  );
+  let doc_texts: Vec<&str> = lexer
+    .doc_comments
+    .iter()
+    .map(|doc_token| &source[doc_token.start..doc_token.end])
+    .collect();
+  assert_eq!(doc_texts[0], "/// Adds two numbers.\r");
+  assert_eq!(doc_texts[3], "/** single */");
+}
+
+#[test]
+fn test_peek_doc_comments_in_lossless_mode() {
+  use crate::core::lexer::decls::{Lexer, TokenType};
+  let source = "/// doc\n// comment\nfn";
+  let mut lexer = Lexer::new_lossless(source);
+  let got_pairs: Vec<(TokenType, String)> = lexer
+    .by_ref()
+    .map(|token| (token.kind, token.raw))
+    .collect();
+  assert_eq!(
+    got_pairs,
+    vec![
+      (TokenType::DocComment, String::from("doc")),
+      (TokenType::Whitespace, String::from("\n")),
+      (TokenType::LineComment, String::from("// comment")),
+      (TokenType::Whitespace, String::from("\n")),
+      (TokenType::Fn, String::from("fn")),
+    ]
+  );
+  assert!(lexer.doc_comments.is_empty());
+}
@@ -10,7 +10,8 @@ fn test_peek_identifiers_and_reserved_words() {
      _32test\n\
      __private\n\
      trait var const\n\
-     fn true",
+     fn true\n\
+     loop while",
   );
   let mut got_pairs = Vec::<(TokenType, String)>::new();
   while let Some(token) = lexer.next() {
@@ -30,6 +31,8 @@ fn test_peek_identifiers_and_reserved_words() {
     (TokenType::Const, String::from("const")),
     (TokenType::Fn, String::from("fn")),
     (TokenType::True, String::from("true")),
+    (TokenType::Loop, String::from("loop")),
+    (TokenType::While, String::from("while")),
   ];
   assert_eq!(got_pairs.len(), answer_pairs.len());
   for i in 0..got_pairs.len() {
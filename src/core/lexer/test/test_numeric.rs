@@ -41,3 +41,52 @@ fn test_peek_numeric_tokens() {
     assert_eq!(got_pairs[i].1, *answer_string);
   }
 }
+
+#[test]
+fn test_peek_numeric_tokens_around_range_operators() {
+  use crate::core::lexer::decls::{Lexer, TokenType};
+  let lexer = Lexer::new("1..3 5..=6 1.5..2.5 0..10");
+  let mut got_pairs = Vec::<(TokenType, String)>::new();
+  for token in lexer {
+    got_pairs.push((token.kind, token.raw));
+  }
+  let answer_pairs: Vec<(TokenType, String)> = vec![
+    (TokenType::DecimalInteger, String::from("1")),
+    (TokenType::DoubleDots, String::from("..")),
+    (TokenType::DecimalInteger, String::from("3")),
+    (TokenType::DecimalInteger, String::from("5")),
+    (TokenType::DoubleDotsEqual, String::from("..=")),
+    (TokenType::DecimalInteger, String::from("6")),
+    (TokenType::Float, String::from("1.5")),
+    (TokenType::DoubleDots, String::from("..")),
+    (TokenType::Float, String::from("2.5")),
+    (TokenType::DecimalInteger, String::from("0")),
+    (TokenType::DoubleDots, String::from("..")),
+    (TokenType::DecimalInteger, String::from("10")),
+  ];
+  assert_eq!(got_pairs.len(), answer_pairs.len());
+  for i in 0..got_pairs.len() {
+    let (answer_type, answer_string) = &answer_pairs[i];
+    assert_eq!(got_pairs[i].0, *answer_type);
+    assert_eq!(got_pairs[i].1, *answer_string);
+  }
+}
+
+#[test]
+fn test_peek_numeric_tokens_followed_by_punctuation() {
+  use crate::core::lexer::decls::{Lexer, TokenType};
+  let lexer = Lexer::new("[0x8F32E,0b101;017]");
+  let got_types: Vec<TokenType> = lexer.map(|token| token.kind).collect();
+  assert_eq!(
+    got_types,
+    vec![
+      TokenType::LeftBracket,
+      TokenType::HexadecimalInteger,
+      TokenType::Comma,
+      TokenType::BinaryInteger,
+      TokenType::Semi,
+      TokenType::OctalInteger,
+      TokenType::RightBracket,
+    ]
+  );
+}
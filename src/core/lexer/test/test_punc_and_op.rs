@@ -8,7 +8,7 @@ fn test_peek_operators_and_punctuation_tokens() {
         ?. ? // this is line comment \n\
         %/  &&(+.../* block comment */\n\
         ||!=*)+=::  >>=..=\n\
-        %=  &=**,)=$:  ;==..<<=",
+        %=  &=**,)=$:  ;==..<<= => **=&&= ||=",
   );
   let mut got_token_types = Vec::<TokenType>::new();
   let mut got_lexer_errors = Vec::<&CompileError>::new();
@@ -50,6 +50,10 @@ fn test_peek_operators_and_punctuation_tokens() {
     TokenType::DoubleEqual,
     TokenType::DoubleDots,
     TokenType::DoubleLeftAngleEqual,
+    TokenType::FatArrow,
+    TokenType::DoubleStarEqual,
+    TokenType::DoubleAmpersandEqual,
+    TokenType::DoubleVerticalEqual,
   ];
   assert_eq!(got_token_types.len(), token_type_answers.len());
   for i in 0..got_token_types.len() {
@@ -64,6 +68,7 @@ fn test_peek_operators_and_punctuation_tokens() {
   if let CompileError::ImbalancedPair {
     pos,
     punc_str: kind,
+    ..
   } = got_lexer_errors[0]
   {
     assert_eq!(*kind, ")");
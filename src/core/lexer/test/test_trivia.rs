@@ -0,0 +1,102 @@
+#[test]
+fn test_peek_trivia_tokens_in_lossless_mode() {
+  use crate::core::lexer::decls::{Lexer, TokenType};
+  let source = "var a = 1; // note\r\n/* block\n comment */\tb /= 2;";
+  let got_pairs: Vec<(TokenType, String)> = Lexer::new_lossless(source)
+    .peek_all_tokens()
+    .into_iter()
+    .map(|token| (token.kind, token.raw))
+    .collect();
+  let answer_pairs: Vec<(TokenType, &str)> = vec![
+    (TokenType::Var, "var"),
+    (TokenType::Whitespace, " "),
+    (TokenType::Identifier, "a"),
+    (TokenType::Whitespace, " "),
+    (TokenType::Equal, "="),
+    (TokenType::Whitespace, " "),
+    (TokenType::DecimalInteger, "1"),
+    (TokenType::Semi, ";"),
+    (TokenType::Whitespace, " "),
+    (TokenType::LineComment, "// note\r"),
+    (TokenType::Whitespace, "\n"),
+    (TokenType::BlockComment, "/* block\n comment */"),
+    (TokenType::Whitespace, "\t"),
+    (TokenType::Identifier, "b"),
+    (TokenType::Whitespace, " "),
+    (TokenType::SlashEqual, "/="),
+    (TokenType::Whitespace, " "),
+    (TokenType::DecimalInteger, "2"),
+    (TokenType::Semi, ";"),
+  ];
+  assert_eq!(
+    got_pairs,
+    answer_pairs
+      .into_iter()
+      .map(|(kind, raw)| (kind, raw.to_string()))
+      .collect::<Vec<(TokenType, String)>>()
+  );
+
+  // trivia are still skipped in the normal mode
+  let got_types: Vec<TokenType> = Lexer::new(source).map(|token| token.kind).collect();
+  assert!(!got_types.iter().any(|kind| matches!(
+    kind,
+    TokenType::Whitespace | TokenType::LineComment | TokenType::BlockComment
+  )));
+  assert_eq!(got_types.len(), 9);
+}
+
+#[test]
+fn test_lossless_tokens_cover_source() {
+  use crate::core::lexer::decls::{Lexer, TokenType};
+  // unknown characters are kept as unknown tokens,
+  // while a malformed number or char literal is still a number or char token
+  let source = "# 名字 = '' + 0x + \"星云\\t\" $ 'ab' /* unclosed";
+  let tokens = Lexer::new_lossless(source).peek_all_tokens();
+  let mut offset = 0;
+  for token in tokens.iter() {
+    assert_eq!(token.start, offset);
+    offset = token.end;
+  }
+  assert_eq!(offset, source.len());
+  let unknown_texts: Vec<&str> = tokens
+    .iter()
+    .filter(|token| token.kind == TokenType::Unknown)
+    .map(|token| &source[token.start..token.end])
+    .collect();
+  assert_eq!(unknown_texts, vec!["#", "$"]);
+  assert!(tokens
+    .iter()
+    .any(|token| token.kind == TokenType::HexadecimalInteger && token.raw == "0x"));
+  let char_texts: Vec<&str> = tokens
+    .iter()
+    .filter(|token| token.kind == TokenType::Char)
+    .map(|token| &source[token.start..token.end])
+    .collect();
+  assert_eq!(char_texts, vec!["''", "'ab'"]);
+}
+
+#[test]
+fn test_lossless_tokens_round_trip_examples() {
+  use crate::core::lexer::decls::Lexer;
+  for source in [
+    include_str!("../../../../examples/src/assignment.n"),
+    include_str!("../../../../examples/src/basic_blocks.n"),
+    include_str!("../../../../examples/src/enums.n"),
+    include_str!("../../../../examples/src/fibonacci.n"),
+    include_str!("../../../../examples/src/function_params.n"),
+    include_str!("../../../../examples/src/match_expr.n"),
+    include_str!("../../../../examples/src/string_utils.n"),
+    include_str!("../../../../examples/src/struct_and_trait.n"),
+    include_str!("../../../../examples/src/use.n"),
+    include_str!("../../../../examples/src/var_decl.n"),
+  ] {
+    let mut lexer = Lexer::new_lossless(source);
+    let text: String = lexer
+      .peek_all_tokens()
+      .iter()
+      .map(|token| &source[token.start..token.end])
+      .collect();
+    assert_eq!(text, source);
+    assert!(lexer.errors.is_empty());
+  }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+use crate::core::shared::diagnostics::{Diagnostic, Label, Severity};
+use crate::core::shared::span::Span;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Lint {
+  // a variable or parameter is never read
+  UnusedVariables,
+  // an entry of `use` statement is never referred
+  UnusedImports,
+  // statements after `return`, `break` or `continue`
+  UnreachableCode,
+  // a `var` is never reassigned, so it can be a `const`
+  PreferConst,
+  // a declaration hides another one with the same name in an enclosing scope
+  ShadowedNames,
+}
+impl Lint {
+  pub const ALL: [Lint; 5] = [
+    Lint::UnusedVariables,
+    Lint::UnusedImports,
+    Lint::UnreachableCode,
+    Lint::PreferConst,
+    Lint::ShadowedNames,
+  ];
+
+  pub fn get_name(&self) -> &'static str {
+    match self {
+      Lint::UnusedVariables => "unused_variables",
+      Lint::UnusedImports => "unused_imports",
+      Lint::UnreachableCode => "unreachable_code",
+      Lint::PreferConst => "prefer_const",
+      Lint::ShadowedNames => "shadowed_names",
+    }
+  }
+
+  pub fn get_default_level(&self) -> LintLevel {
+    LintLevel::Warn
+  }
+
+  /// Resolves a lint name, `-` is accepted as `_`, and `warnings` stands for all lints.
+  pub fn resolve_name(name: &str) -> Option<Vec<Lint>> {
+    let name = name.replace('-', "_");
+    if name == "warnings" {
+      return Some(Lint::ALL.to_vec());
+    }
+    Lint::ALL
+      .iter()
+      .find(|lint| lint.get_name() == name)
+      .map(|lint| vec![*lint])
+  }
+}
+impl Display for Lint {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(f, "{}", self.get_name())
+  }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum LintLevel {
+  Allow,
+  Warn,
+  // reported as an error, fails the compilation
+  Deny,
+}
+impl LintLevel {
+  /// Parses the level in the `[lints]` table of `universe.toml`.
+  pub fn from_name(name: &str) -> Option<LintLevel> {
+    match name {
+      "allow" => Some(LintLevel::Allow),
+      "warn" => Some(LintLevel::Warn),
+      "deny" => Some(LintLevel::Deny),
+      _ => None,
+    }
+  }
+
+  /// Parses the command line flags `-A`, `-W` and `-D`.
+  pub fn from_flag(flag: &str) -> Option<LintLevel> {
+    match flag {
+      "-A" => Some(LintLevel::Allow),
+      "-W" => Some(LintLevel::Warn),
+      "-D" => Some(LintLevel::Deny),
+      _ => None,
+    }
+  }
+}
+
+/// The level of every lint, starts from the default levels,
+/// then is overridden by `universe.toml` and the command line flags in order.
+#[derive(Debug, Clone)]
+pub struct LintConfig {
+  levels: HashMap<Lint, LintLevel>,
+}
+impl Default for LintConfig {
+  fn default() -> Self {
+    LintConfig {
+      levels: Lint::ALL
+        .iter()
+        .map(|lint| (*lint, lint.get_default_level()))
+        .collect(),
+    }
+  }
+}
+impl LintConfig {
+  pub fn get_level(&self, lint: Lint) -> LintLevel {
+    self.levels[&lint]
+  }
+
+  pub fn set_level(&mut self, lints: &[Lint], level: LintLevel) {
+    for lint in lints {
+      self.levels.insert(*lint, level);
+    }
+  }
+
+  /// Applies the entries of the `[lints]` table, such as `unused_variables = "allow"`.
+  pub fn apply_table(&mut self, table: &HashMap<String, String>) -> Result<(), String> {
+    // `warnings` goes first, so the levels of single lints override it
+    let mut entries: Vec<(&String, &String)> = table.iter().collect();
+    entries.sort_by_key(|(name, _)| (name.as_str() != "warnings", *name));
+    for (name, level_name) in entries {
+      let Some(lints) = Lint::resolve_name(name) else {
+        return Err(format!("unknown lint '{}' in [lints]", name));
+      };
+      let Some(level) = LintLevel::from_name(level_name) else {
+        return Err(format!(
+          "invalid level '{}' of lint '{}', expected one of: allow, warn, deny",
+          level_name, name
+        ));
+      };
+      self.set_level(&lints, level);
+    }
+    Ok(())
+  }
+}
+
+/// A problem found by a lint, it becomes a diagnostic by the configured level.
+#[derive(Debug, Clone)]
+pub struct LintReport {
+  pub lint: Lint,
+  pub message: String,
+  pub label: Label,
+  pub secondary: Vec<Label>,
+  pub help: Option<String>,
+}
+impl LintReport {
+  pub fn new(lint: Lint, message: String, span: Span, label: &str) -> LintReport {
+    LintReport {
+      lint,
+      message,
+      label: Label::new(span, label),
+      secondary: vec![],
+      help: None,
+    }
+  }
+
+  pub fn with_secondary(mut self, span: Span, label: &str) -> LintReport {
+    self.secondary.push(Label::new(span, label));
+    self
+  }
+
+  pub fn with_help(mut self, help: &str) -> LintReport {
+    self.help = Some(help.to_string());
+    self
+  }
+
+  /// Returns `None` if the lint is allowed.
+  pub fn to_diagnostic(&self, level: LintLevel) -> Option<Diagnostic> {
+    let (severity, flag) = match level {
+      LintLevel::Allow => return None,
+      LintLevel::Warn => (Severity::Warning, "-W"),
+      LintLevel::Deny => (Severity::Error, "-D"),
+    };
+    Some(Diagnostic {
+      code: self.lint.get_name(),
+      severity,
+      message: self.message.clone(),
+      primary: self.label.clone(),
+      secondary: self.secondary.clone(),
+      notes: vec![format!(
+        "`{} {}` is in effect, use `-A {}` to allow it",
+        flag, self.lint, self.lint
+      )],
+      help: self.help.clone(),
+    })
+  }
+}
@@ -0,0 +1,474 @@
+use crate::core::lint::decls::{Lint, LintReport};
+use crate::core::shared::ast::expressions::{
+  ArrayDestructAssign, ArrayDestructRest, AssignmentLeftHand, Expression, ExpressionWithBlock,
+  ForLoopAlias, MatchArmPattern, MatchSingleArm, NamePathHead, NormalExpression,
+};
+use crate::core::shared::ast::statements::{
+  DeclarationPattern, FunctionDeclaration, Statement, TopStatement, UseTree,
+};
+use crate::core::shared::ast::Identifier;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BindingKind {
+  Import,
+  Variable {
+    is_const: bool,
+    has_initializer: bool,
+  },
+  Parameter,
+  LoopAlias,
+}
+
+struct Binding {
+  name: Identifier,
+  kind: BindingKind,
+  is_used: bool,
+  is_reassigned: bool,
+}
+
+/// Walks the AST of a whole file after a successful parsing, and reports the problems
+/// which are not errors but probably mistakes. All lints are reported here,
+/// the levels are applied by the caller.
+pub struct Linter {
+  bindings: Vec<Binding>,
+  /// Indexes of the bindings declared in each scope, from the outermost to the innermost.
+  scopes: Vec<Vec<usize>>,
+
+  pub reports: Vec<LintReport>,
+}
+
+impl Linter {
+  pub fn new() -> Linter {
+    Linter {
+      bindings: vec![],
+      scopes: vec![],
+      reports: vec![],
+    }
+  }
+
+  /// Lints the top statements of a file, the reports are sorted by their positions.
+  pub fn lint_program(mut self, top_statements: &[TopStatement]) -> Vec<LintReport> {
+    self.scopes.push(vec![]);
+    // imports are visible in the whole file, no matter where they are declared
+    for top_statement in top_statements {
+      if let TopStatement::UseStatement(use_tree, ..) = top_statement {
+        self.declare_use_tree(use_tree);
+      }
+    }
+    for top_statement in top_statements {
+      match top_statement {
+        TopStatement::FunctionDeclaration(function_decl) => self.lint_function(function_decl),
+        TopStatement::ImplDeclaration {
+          trait_name,
+          struct_name,
+          methods,
+          ..
+        } => {
+          if let Some(trait_name) = trait_name {
+            self.use_name(trait_name);
+          }
+          self.use_name(struct_name);
+          for (method, _) in methods {
+            self.lint_function(method);
+          }
+        }
+        _ => {}
+      }
+    }
+    self.pop_scope();
+    self.reports.sort_by_key(|report| report.label.span.start);
+    self.reports
+  }
+
+  fn declare_use_tree(&mut self, use_tree: &UseTree) {
+    match use_tree {
+      UseTree::Path { path, alias, .. } => {
+        if let Some(name) = alias.as_ref().or(path.last()) {
+          self.declare(name, BindingKind::Import);
+        }
+      }
+      UseTree::Select { item, .. } => self.declare_use_tree(item),
+      UseTree::Group { children, .. } => {
+        for child in children {
+          self.declare_use_tree(child);
+        }
+      }
+    }
+  }
+
+  fn declare(&mut self, name: &Identifier, kind: BindingKind) {
+    if kind != BindingKind::Import {
+      if let Some(previous) = self.lookup(&name.name) {
+        let previous = &self.bindings[previous];
+        if previous.kind != BindingKind::Import {
+          let previous_span = previous.name.span;
+          self.reports.push(
+            LintReport::new(
+              Lint::ShadowedNames,
+              format!("`{}` shadows a previous declaration", name.name),
+              name.span,
+              "shadows the previous one",
+            )
+            .with_secondary(previous_span, "previously declared here")
+            .with_help("rename it if both of them are needed"),
+          );
+        }
+      }
+    }
+    self.bindings.push(Binding {
+      name: name.clone(),
+      kind,
+      is_used: false,
+      is_reassigned: false,
+    });
+    let index = self.bindings.len() - 1;
+    if let Some(scope) = self.scopes.last_mut() {
+      scope.push(index);
+    }
+  }
+
+  /// Finds the binding of the name from the innermost scope.
+  fn lookup(&self, name: &str) -> Option<usize> {
+    self
+      .scopes
+      .iter()
+      .rev()
+      .flat_map(|scope| scope.iter().rev())
+      .find(|index| self.bindings[**index].name.name == name)
+      .copied()
+  }
+
+  fn use_name(&mut self, name: &Identifier) {
+    if let Some(index) = self.lookup(&name.name) {
+      self.bindings[index].is_used = true;
+    }
+  }
+
+  /// A compound assignment such as `a += 1` also reads the variable.
+  fn assign_name(&mut self, name: &Identifier, is_compound: bool) {
+    if let Some(index) = self.lookup(&name.name) {
+      let binding = &mut self.bindings[index];
+      binding.is_reassigned = true;
+      binding.is_used = binding.is_used || is_compound;
+    }
+  }
+
+  fn pop_scope(&mut self) {
+    let Some(scope) = self.scopes.pop() else {
+      return;
+    };
+    for index in scope {
+      let binding = &self.bindings[index];
+      let name = &binding.name;
+      if binding.kind == BindingKind::Import {
+        if !binding.is_used {
+          self.reports.push(
+            LintReport::new(
+              Lint::UnusedImports,
+              format!("unused import `{}`", name.name),
+              name.span,
+              "never used",
+            )
+            .with_help("remove it from the use statement"),
+          );
+        }
+        continue;
+      }
+      if !binding.is_used && !name.name.starts_with('_') {
+        self.reports.push(
+          LintReport::new(
+            Lint::UnusedVariables,
+            format!("unused variable `{}`", name.name),
+            name.span,
+            "never read",
+          )
+          .with_help(&format!(
+            "if this is intentional, prefix it with an underscore: `_{}`",
+            name.name
+          )),
+        );
+      }
+      if binding.kind
+        == (BindingKind::Variable {
+          is_const: false,
+          has_initializer: true,
+        })
+        && !binding.is_reassigned
+      {
+        self.reports.push(
+          LintReport::new(
+            Lint::PreferConst,
+            format!("variable `{}` is never reassigned", name.name),
+            name.span,
+            "declared with `var`",
+          )
+          .with_help("declare it with `const` instead"),
+        );
+      }
+    }
+  }
+
+  fn lint_function(&mut self, function_decl: &FunctionDeclaration) {
+    self.scopes.push(vec![]);
+    for param in function_decl.params() {
+      self.declare(param, BindingKind::Parameter);
+    }
+    if let Some(rest_param) = function_decl.rest_param() {
+      self.declare(rest_param, BindingKind::Parameter);
+    }
+    self.lint_statements(function_decl.body());
+    self.pop_scope();
+  }
+
+  /// The statements after `return`, `break` or `continue` are never executed,
+  /// only the first one is reported in each block.
+  fn lint_statements(&mut self, statements: &[Statement]) {
+    let mut jumped_by: Option<&str> = None;
+    let mut is_reported = false;
+    for statement in statements {
+      if let (Some(keyword), false) = (jumped_by, is_reported) {
+        self.reports.push(
+          LintReport::new(
+            Lint::UnreachableCode,
+            String::from("unreachable statement"),
+            statement.span(),
+            &format!("never executed after `{}`", keyword),
+          )
+          .with_help(&format!("remove it, or move it before the `{}`", keyword)),
+        );
+        is_reported = true;
+      }
+      match statement {
+        Statement::ReturnStatement(..) => jumped_by = jumped_by.or(Some("return")),
+        Statement::BreakStatement(..) => jumped_by = jumped_by.or(Some("break")),
+        Statement::ContinueStatement(_) => jumped_by = jumped_by.or(Some("continue")),
+        _ => {}
+      }
+      self.lint_statement(statement);
+    }
+  }
+
+  fn lint_statement(&mut self, statement: &Statement) {
+    match statement {
+      Statement::ExpressionStatement(expression, _) | Statement::TrailingExpression(expression) => {
+        self.lint_expression(expression)
+      }
+      Statement::VariableDeclaration {
+        is_const, decls, ..
+      } => {
+        for (pattern, initializer) in decls {
+          // the initializer is evaluated before the variable is declared
+          if let Some(initializer) = initializer {
+            self.lint_expression(initializer);
+          }
+          let kind = BindingKind::Variable {
+            is_const: *is_const,
+            has_initializer: initializer.is_some(),
+          };
+          match pattern {
+            DeclarationPattern::Identifier(name) => self.declare(name, kind),
+            DeclarationPattern::Destruct(destruct) => self.declare_destruct(destruct, kind),
+          }
+        }
+      }
+      Statement::ReturnStatement(value, _) | Statement::BreakStatement(value, _) => {
+        if let Some(value) = value {
+          self.lint_expression(value);
+        }
+      }
+      Statement::ContinueStatement(_) => {}
+      Statement::FunctionDeclaration(function_decl) => self.lint_function(function_decl),
+    }
+  }
+
+  fn declare_destruct(&mut self, destruct: &ArrayDestructAssign, kind: BindingKind) {
+    for var in destruct.vars.iter() {
+      self.declare(var, kind);
+    }
+    match &destruct.rest {
+      Some(ArrayDestructRest::Identifier(rest)) => self.declare(rest, kind),
+      Some(ArrayDestructRest::ChildRest(child)) => self.declare_destruct(child, kind),
+      None => {}
+    }
+  }
+
+  fn assign_destruct(&mut self, destruct: &ArrayDestructAssign) {
+    for var in destruct.vars.iter() {
+      self.assign_name(var, false);
+    }
+    match &destruct.rest {
+      Some(ArrayDestructRest::Identifier(rest)) => self.assign_name(rest, false),
+      Some(ArrayDestructRest::ChildRest(child)) => self.assign_destruct(child),
+      None => {}
+    }
+  }
+
+  fn lint_expression(&mut self, expression: &Expression) {
+    match expression {
+      Expression::NormalExpression(normal_expression) => {
+        self.lint_normal_expression(normal_expression)
+      }
+      Expression::ExpressionWithBlock(block_expression) => {
+        self.lint_expression_with_block(block_expression)
+      }
+    }
+  }
+
+  fn lint_normal_expression(&mut self, expression: &NormalExpression) {
+    match expression {
+      NormalExpression::Grouping(inner, _) | NormalExpression::AwaitExpression(inner, _) => {
+        self.lint_expression(inner)
+      }
+      NormalExpression::SimpleLiteral(..) => {}
+      NormalExpression::ArrayLiteral(elements, rest, _) => {
+        for element in elements {
+          self.lint_expression(element);
+        }
+        if let Some(rest) = rest {
+          self.lint_normal_expression(rest);
+        }
+      }
+      NormalExpression::NamePathExpression(name_path) => {
+        if let NamePathHead::Identifier(head) = &name_path.head {
+          self.use_name(head);
+        }
+      }
+      NormalExpression::LambdaExpression(lambda) => {
+        self.scopes.push(vec![]);
+        for param in lambda.params.iter() {
+          self.declare(param, BindingKind::Parameter);
+        }
+        if let Some(rest_param) = &lambda.rest_param {
+          self.declare(rest_param, BindingKind::Parameter);
+        }
+        self.lint_statements(&lambda.body);
+        self.pop_scope();
+      }
+      NormalExpression::StructInitExpression(struct_name, fields, _) => {
+        if let Some(struct_name) = struct_name {
+          self.use_name(struct_name);
+        }
+        for (_, value) in fields {
+          self.lint_expression(value);
+        }
+      }
+      NormalExpression::NewExpression(struct_name, _) => self.use_name(struct_name),
+      NormalExpression::GetExpression(source, ..)
+      | NormalExpression::UnaryExpression(source, ..) => self.lint_normal_expression(source),
+      NormalExpression::CallExpression(callee, arguments, ..) => {
+        self.lint_normal_expression(callee);
+        for argument in arguments {
+          self.lint_expression(argument);
+        }
+      }
+      NormalExpression::IndexExpression(source, index, ..) => {
+        self.lint_normal_expression(source);
+        self.lint_expression(index);
+      }
+      NormalExpression::BinaryExpression(left, _, right, _)
+      | NormalExpression::RangeExpression(left, right, ..) => {
+        self.lint_normal_expression(left);
+        self.lint_normal_expression(right);
+      }
+      NormalExpression::AssignmentExpression(left_hand, value, _) => {
+        self.lint_expression(value);
+        self.lint_assignment_left_hand(left_hand, false);
+      }
+      NormalExpression::CompoundAssignmentExpression(left_hand, _, value, _) => {
+        self.lint_expression(value);
+        self.lint_assignment_left_hand(left_hand, true);
+      }
+    }
+  }
+
+  fn lint_assignment_left_hand(&mut self, left_hand: &AssignmentLeftHand, is_compound: bool) {
+    match left_hand {
+      AssignmentLeftHand::Identifier(name) => self.assign_name(name, is_compound),
+      AssignmentLeftHand::Destruct(destruct) => self.assign_destruct(destruct),
+      // `a.b = 1` and `a[0] = 1` read the variable `a` rather than reassigning it
+      AssignmentLeftHand::GetExpression(target) | AssignmentLeftHand::IndexExpression(target) => {
+        self.lint_expression(target)
+      }
+    }
+  }
+
+  fn lint_expression_with_block(&mut self, expression: &ExpressionWithBlock) {
+    match expression {
+      ExpressionWithBlock::BareBlock(statements, _) => {
+        self.scopes.push(vec![]);
+        self.lint_statements(statements);
+        self.pop_scope();
+      }
+      ExpressionWithBlock::IfExpression {
+        condition,
+        then_block,
+        else_if,
+        else_block,
+        ..
+      } => {
+        self.lint_expression(condition);
+        self.lint_statement(then_block);
+        for (else_if_condition, else_if_block) in else_if {
+          self.lint_expression(else_if_condition);
+          self.lint_statement(else_if_block);
+        }
+        if let Some(else_block) = else_block {
+          self.lint_statement(else_block);
+        }
+      }
+      ExpressionWithBlock::LoopExpression { block, .. } => self.lint_statement(block),
+      ExpressionWithBlock::WhileExpression {
+        condition, block, ..
+      } => {
+        self.lint_expression(condition);
+        self.lint_statement(block);
+      }
+      ExpressionWithBlock::ForEachExpression {
+        index_var,
+        element_var,
+        iterable,
+        block,
+        ..
+      } => {
+        self.lint_expression(iterable);
+        self.scopes.push(vec![]);
+        for alias in index_var.iter().chain(std::iter::once(element_var)) {
+          if let ForLoopAlias::Named(name) = alias {
+            self.declare(name, BindingKind::LoopAlias);
+          }
+        }
+        self.lint_statement(block);
+        self.pop_scope();
+      }
+      ExpressionWithBlock::MatchExpression {
+        expression, arms, ..
+      } => {
+        self.lint_expression(expression);
+        for (pattern, body) in arms {
+          let single_arms = match pattern {
+            MatchArmPattern::Single(single_arm) => vec![single_arm],
+            MatchArmPattern::Mutiple(single_arms, _) => single_arms.iter().collect(),
+            MatchArmPattern::RangePattern(start, end, ..) => {
+              self.lint_normal_expression(start);
+              self.lint_normal_expression(end);
+              vec![]
+            }
+            MatchArmPattern::Fallback(_) => vec![],
+          };
+          for single_arm in single_arms {
+            match single_arm {
+              MatchSingleArm::Identifier(name) => self.use_name(name),
+              MatchSingleArm::Path(name_path) => {
+                if let NamePathHead::Identifier(head) = &name_path.head {
+                  self.use_name(head);
+                }
+              }
+              MatchSingleArm::Literal(..) => {}
+            }
+          }
+          self.lint_statement(body);
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,3 @@
+pub mod decls;
+pub mod impls;
+mod test;
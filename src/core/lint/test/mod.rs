@@ -0,0 +1,2 @@
+mod test_lint_config;
+mod test_lints;
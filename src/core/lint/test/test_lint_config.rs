@@ -0,0 +1,101 @@
+#[test]
+fn test_parse_manifest() {
+  use crate::core::universe::parse_manifest;
+  let tables = parse_manifest(
+    r#"
+    # the package
+    [package]
+    name = "nebula-demo" # comment after value
+    version = "0.1.0"
+    keywords = [
+      "demo", # the brackets in comments are ignored: ]
+      "[lints]",
+    ]
+    authors = [{ name = "someone" }]
+    description = """
+    A demo package.
+    """
+    metadata = { docs = [
+      "ignored"
+    ] }
+
+    [[bin]]
+    name = "demo"
+
+    [lints]
+    unused-variables = "allow"
+    warnings = "deny"
+    "#,
+  )
+  .unwrap();
+  assert_eq!(tables["package"]["name"], "nebula-demo");
+  assert_eq!(tables["package"]["version"], "0.1.0");
+  assert_eq!(tables["package"].len(), 2);
+  assert_eq!(tables["lints"]["unused-variables"], "allow");
+  assert_eq!(tables["lints"]["warnings"], "deny");
+
+  assert!(parse_manifest("[lints\n").is_err());
+  assert!(parse_manifest("[lints]\nunused_variables\n").is_err());
+  assert!(parse_manifest("[lints]\nunused_variables = \"allow\n").is_err());
+  assert!(parse_manifest("[lints]\nunused_variables = [\"allow\"]\n").is_err());
+  // only the `[lints]` table must be well-formed
+  assert!(parse_manifest("[package]\nname\n[lints]\nwarnings = \"deny\"\n").is_ok());
+}
+
+#[test]
+fn test_lint_config_levels() {
+  use crate::core::lint::decls::{Lint, LintConfig, LintLevel};
+  use std::collections::HashMap;
+
+  let mut lint_config = LintConfig::default();
+  for lint in Lint::ALL {
+    assert_eq!(lint_config.get_level(lint), LintLevel::Warn);
+  }
+
+  // `warnings` is applied before other names in the table
+  let table = HashMap::from([
+    (String::from("warnings"), String::from("deny")),
+    (String::from("unused-variables"), String::from("allow")),
+  ]);
+  lint_config.apply_table(&table).unwrap();
+  assert_eq!(
+    lint_config.get_level(Lint::UnusedVariables),
+    LintLevel::Allow
+  );
+  assert_eq!(lint_config.get_level(Lint::ShadowedNames), LintLevel::Deny);
+
+  // command line flags come after the manifest
+  lint_config.set_level(
+    &Lint::resolve_name("shadowed_names").unwrap(),
+    LintLevel::Warn,
+  );
+  assert_eq!(lint_config.get_level(Lint::ShadowedNames), LintLevel::Warn);
+
+  let table = HashMap::from([(String::from("unused_code"), String::from("allow"))]);
+  assert!(lint_config.apply_table(&table).is_err());
+  let table = HashMap::from([(String::from("prefer_const"), String::from("forbid"))]);
+  assert!(lint_config.apply_table(&table).is_err());
+}
+
+#[test]
+fn test_lint_report_to_diagnostic() {
+  use crate::core::{
+    lint::decls::{Lint, LintLevel, LintReport},
+    shared::{
+      diagnostics::Severity,
+      span::{FileId, Span},
+    },
+  };
+  let report = LintReport::new(
+    Lint::PreferConst,
+    String::from("variable `total` is never reassigned"),
+    Span::new(FileId(0), 4, 9),
+    "declared with `var`",
+  );
+  assert!(report.to_diagnostic(LintLevel::Allow).is_none());
+  let warning = report.to_diagnostic(LintLevel::Warn).unwrap();
+  assert_eq!(warning.severity, Severity::Warning);
+  assert_eq!(warning.code, "prefer_const");
+  let error = report.to_diagnostic(LintLevel::Deny).unwrap();
+  assert_eq!(error.severity, Severity::Error);
+}
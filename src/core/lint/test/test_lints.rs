@@ -0,0 +1,141 @@
+#[cfg(test)]
+fn lint_source(source: &str) -> Vec<String> {
+  use crate::core::{lint::impls::Linter, parser::impls::Parser};
+  let mut parser = Parser::new(source);
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty(), "{:?}", errors);
+  Linter::new()
+    .lint_program(&top_statements)
+    .iter()
+    .map(|report| format!("{}: {}", report.lint, report.message))
+    .collect()
+}
+
+#[test]
+fn test_lint_unused_variables() {
+  let reports = lint_source(
+    r#"
+    fn main(name, _ignored, ...rest) {
+      const greeting = "Hello";
+      const unused = 1;
+      printf("{} {}", greeting, name);
+      for index, item in items { }
+      var callback = $: x -> 1;
+      callback();
+    }"#,
+  );
+  assert_eq!(
+    reports,
+    vec![
+      "unused_variables: unused variable `rest`",
+      "unused_variables: unused variable `unused`",
+      "unused_variables: unused variable `index`",
+      "unused_variables: unused variable `item`",
+      "prefer_const: variable `callback` is never reassigned",
+      "unused_variables: unused variable `x`",
+    ]
+  );
+}
+
+#[test]
+fn test_lint_unused_imports() {
+  let reports = lint_source(
+    r#"
+    use std::fs;
+    use std::io as stdio;
+    use crate::enums.ExceptionResponse;
+    use std::strings { snake_to_camel, camel_to_snake as to_snake };
+    impl Display for People { }
+    use crate::people.People;
+    use std::fmt.Display;
+    fn main {
+      stdio::println(snake_to_camel("a_b"));
+      match response {
+        ExceptionResponse::NetworkIssue => 1,
+        _ => 0,
+      }
+    }"#,
+  );
+  assert_eq!(
+    reports,
+    vec![
+      "unused_imports: unused import `fs`",
+      "unused_imports: unused import `to_snake`",
+    ]
+  );
+}
+
+#[test]
+fn test_lint_unreachable_code() {
+  let reports = lint_source(
+    r#"
+    fn main {
+      loop {
+        break;
+        println("unreachable");
+        println("reported once");
+      }
+      if done {
+        return 1;
+      }
+      println("reachable");
+      return 0;
+      fn nested { }
+    }"#,
+  );
+  assert_eq!(
+    reports,
+    vec![
+      "unreachable_code: unreachable statement",
+      "unreachable_code: unreachable statement",
+    ]
+  );
+}
+
+#[test]
+fn test_lint_prefer_const() {
+  let reports = lint_source(
+    r#"
+    fn main {
+      var count = 0, total = 0, later;
+      var [first, second] = pair;
+      count += 1;
+      [first, second] = [second, first];
+      later = total;
+      println(count, later);
+    }"#,
+  );
+  assert_eq!(
+    reports,
+    vec!["prefer_const: variable `total` is never reassigned"]
+  );
+}
+
+#[test]
+fn test_lint_shadowed_names() {
+  let reports = lint_source(
+    r#"
+    use std::fs;
+    fn main(value) {
+      const fs = value;
+      if fs {
+        const value = 1;
+        println(value);
+      }
+      for value in values {
+        println(value);
+      }
+    }
+    fn other(value) {
+      println(value);
+    }"#,
+  );
+  assert_eq!(
+    reports,
+    vec![
+      "unused_imports: unused import `fs`",
+      "shadowed_names: `value` shadows a previous declaration",
+      "shadowed_names: `value` shadows a previous declaration",
+    ]
+  );
+}
@@ -1,4 +1,7 @@
+pub mod doc;
 pub mod entry;
 pub mod lexer;
+pub mod lint;
 pub mod parser;
 pub mod shared;
+pub mod universe;
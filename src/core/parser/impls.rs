@@ -1,7 +1,16 @@
-use crate::core::shared::ast::expressions::{NamePathExpression, NamePathHead};
+use crate::core::shared::ast::expressions::{
+  ArrayDestructAssign, ArrayDestructRest, AssignmentLeftHand, BinaryOperator,
+  CompoundAssignmentOperator, ExpressionWithBlock, ForLoopAlias, LambdaExpression, MatchArmPattern,
+  MatchSingleArm, NamePathExpression, NamePathHead, UnaryOperator,
+};
+use crate::core::shared::ast::statements::{
+  DeclarationPattern, FunctionDeclaration, FunctionSignature, Statement, StructField, TopStatement,
+  UseTree,
+};
 use crate::core::shared::ast::Identifier;
+use crate::core::shared::span::{FileId, Span};
 use crate::core::{
-  lexer::decls::{Lexer, Token, TokenType},
+  lexer::decls::{Lexer, PairPunctuation, Token, TokenType},
   shared::{
     ast::{
       expressions::{Expression, NormalExpression, SimpleLiteral},
@@ -11,6 +20,7 @@ use crate::core::{
     nebula_interal_err,
   },
 };
+use crate::utils::log;
 
 struct ParsingTokenMeta {
   raw: String,
@@ -18,16 +28,39 @@ struct ParsingTokenMeta {
   pos: Position,
 }
 
+/// Properties: is async, method name, has `self` receiver, params, rest param
+type MethodHead = (bool, Identifier, bool, Vec<Identifier>, Option<Identifier>);
+
 pub struct Parser<'a> {
   last_token: Option<Token>,
   current_token: Option<Token>,
 
+  /// The file being parsed, recorded in the spans of AST nodes.
+  file_id: FileId,
+
   /// Obtain a lexer inside parser to peek next token
   pub lexer: Lexer<'a>,
 
   /// All the errors that occurred during the parsing.<br>
   /// (Adopted the errors that occurred during the lexing.)
   pub errors: Vec<CompileError>,
+
+  /// Struct init expressions are forbidden in the head of with-block-expressions,
+  /// otherwise `if x { }` would be ambiguous.
+  allow_struct_init: bool,
+
+  /// `break` and `continue` are only allowed in the body of loops.
+  is_in_loop: bool,
+  /// `return` is only allowed in the body of functions, methods and lambdas.
+  is_in_function: bool,
+
+  /// The index of the first doc comment in `lexer.doc_comments` which is not taken
+  /// or skipped yet, the doc comments are in source order.
+  doc_comments_cursor: usize,
+
+  /// Errors of array literals with a rest element, which are collected
+  /// unless the array turns out to be the left-hand side of a destructing assignment.
+  rest_array_errors: Vec<CompileError>,
 }
 
 impl<'a> Parser<'a> {
@@ -36,10 +69,15 @@ impl<'a> Parser<'a> {
     ref_last_token: &mut Option<Token>,
     ref_current_token: &mut Option<Token>,
   ) {
-    if let Some(current_token) = lexer.peek_next_token() {
-      let last_token = (*ref_current_token).take();
-      *ref_last_token = last_token;
-      *ref_current_token = Some(current_token);
+    // the lexer gives nothing on a malformed token (the error has been collected),
+    // keeps lexing until a valid token or EOF is received
+    loop {
+      if let Some(current_token) = lexer.peek_next_token() {
+        let last_token = (*ref_current_token).take();
+        *ref_last_token = last_token;
+        *ref_current_token = Some(current_token);
+        return;
+      }
     }
   }
 
@@ -69,6 +107,8 @@ impl<'a> Parser<'a> {
       Some(Position {
         line: token.line,
         col: token.col,
+        start: token.start,
+        end: token.end,
       })
     } else {
       None
@@ -97,20 +137,30 @@ impl<'a> Parser<'a> {
     ref_current_token: &mut Option<Token>,
     current_token_desc: &str,
   ) -> ParsingTokenMeta {
-    let current_pos = Parser::get_token_pos(ref_current_token).expect(
-      nebula_interal_err(format!("expect receiving position of {}!", current_token_desc).as_str())
-        .as_str(),
-    );
-    let current_kind = Parser::get_token_kind(ref_current_token).expect(
-      nebula_interal_err(
-        format!("expect receiving token type of {}!", current_token_desc).as_str(),
-      )
-      .as_str(),
-    );
-    let current_raw = Parser::get_token_raw(ref_current_token).expect(
-      nebula_interal_err(format!("expect receiving token raw of {}!", current_token_desc).as_str())
-        .as_str(),
+    let Some(current_token) = ref_current_token else {
+      // the lexer always gives an EOF token at the end, so it should never happen
+      log::error(&nebula_interal_err(
+        format!("expect receiving {} but got nothing!", current_token_desc).as_str(),
+      ));
+      return ParsingTokenMeta {
+        raw: String::new(),
+        kind: TokenType::EOF,
+        pos: Position::new(
+          ref_lexer.cur_line,
+          ref_lexer.cur_col,
+          ref_lexer.offset_cursor,
+          ref_lexer.offset_cursor,
+        ),
+      };
+    };
+    let current_pos = Position::new(
+      current_token.line,
+      current_token.col,
+      current_token.start,
+      current_token.end,
     );
+    let current_kind = current_token.kind;
+    let current_raw = current_token.raw.clone();
     Parser::move_to_next_token(ref_lexer, ref_last_token, ref_current_token);
     ParsingTokenMeta {
       pos: current_pos,
@@ -124,32 +174,186 @@ impl<'a> Parser<'a> {
     ref_current_token: &Option<Token>,
     create_err_by_pos: fn(pos: Position) -> CompileError,
   ) {
-    let current_token_pos = Parser::get_token_pos(ref_current_token)
-      .expect("expect recieving position of current token !");
-    ref_errors.push(create_err_by_pos(current_token_pos));
+    if let Some(current_token_pos) = Parser::get_token_pos(ref_current_token) {
+      ref_errors.push(create_err_by_pos(current_token_pos));
+    }
+  }
+
+  fn get_token_display_name(ref_token: &Option<Token>) -> String {
+    match ref_token {
+      Some(token) if token.kind == TokenType::EOF => String::from("end of file"),
+      Some(token) => token.raw.clone(),
+      None => String::from("nothing"),
+    }
+  }
+
+  /// Moves over the current token if it's the expected one,
+  /// otherwise collects an error and stays at current token.
+  fn consume_expected_token(
+    &mut self,
+    token_type: TokenType,
+    expected_desc: &str,
+  ) -> Option<ParsingTokenMeta> {
+    if Parser::match_current_token_type(&self.current_token, token_type) {
+      return Some(Parser::get_current_token_meta_and_move_next(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+        expected_desc,
+      ));
+    }
+    self.collect_expected_err_on_current_token(expected_desc);
+    None
+  }
+
+  /// error: expected something but found the current token
+  fn collect_expected_err_on_current_token(&mut self, expected_desc: &str) {
+    if let Some(current_pos) = Parser::get_token_pos(&self.current_token) {
+      self.errors.push(CompileError::ExpectedToken {
+        expected: expected_desc.to_string(),
+        found: Parser::get_token_display_name(&self.current_token),
+        pos: current_pos,
+      });
+    }
+  }
+
+  fn get_span(&self, pos: Position) -> Span {
+    Span::from_pos(self.file_id, pos)
+  }
+
+  /// The byte offset where the current token starts, which is the start of the node
+  /// being parsed.
+  fn get_current_start(&self) -> usize {
+    match &self.current_token {
+      Some(current_token) => current_token.start,
+      None => self.lexer.offset_cursor,
+    }
+  }
+
+  /// The span of a node which starts at the given byte offset,
+  /// and ends with the last consumed token.
+  fn get_span_from(&self, start: usize) -> Span {
+    let end = match &self.last_token {
+      Some(last_token) => last_token.end.max(start),
+      None => start,
+    };
+    Span::new(self.file_id, start, end)
+  }
+
+  /// The doc comments between the last consumed token and the current one,
+  /// which document the declaration starting at the current token.
+  /// The doc comments before them are skipped, since they don't document anything.
+  fn get_doc_comment(&mut self) -> Option<String> {
+    let after = self
+      .last_token
+      .as_ref()
+      .map_or(0, |last_token| last_token.end);
+    let before = self.get_current_start();
+    let doc_comments = &self.lexer.doc_comments;
+    let mut cursor = self.doc_comments_cursor;
+    while cursor < doc_comments.len() && doc_comments[cursor].start < after {
+      cursor += 1;
+    }
+    let mut doc_lines: Vec<&str> = vec![];
+    while cursor < doc_comments.len() && doc_comments[cursor].end <= before {
+      doc_lines.push(doc_comments[cursor].raw.as_str());
+      cursor += 1;
+    }
+    self.doc_comments_cursor = cursor;
+    if doc_lines.is_empty() {
+      None
+    } else {
+      Some(doc_lines.join("\n"))
+    }
+  }
+
+  fn get_identifier(&self, identifier_token: ParsingTokenMeta) -> Identifier {
+    Identifier {
+      name: identifier_token.raw,
+      span: self.get_span(identifier_token.pos),
+    }
+  }
+
+  fn consume_identifier(&mut self, expected_desc: &str) -> Option<Identifier> {
+    let identifier_token = self.consume_expected_token(TokenType::Identifier, expected_desc)?;
+    Some(self.get_identifier(identifier_token))
+  }
+
+  /// Parsing with struct init expressions allowed or not,
+  /// the restriction will be restored after parsing.
+  fn with_struct_init_allowed<T>(
+    &mut self,
+    allowed: bool,
+    parse: impl FnOnce(&mut Self) -> Option<T>,
+  ) -> Option<T> {
+    let last_allowed = std::mem::replace(&mut self.allow_struct_init, allowed);
+    let parsed = parse(self);
+    self.allow_struct_init = last_allowed;
+    parsed
+  }
+
+  /// Parsing the body of a loop or a function, the context will be restored after parsing.
+  fn with_body_context<T>(
+    &mut self,
+    is_in_loop: bool,
+    is_in_function: bool,
+    parse: impl FnOnce(&mut Self) -> Option<T>,
+  ) -> Option<T> {
+    let last_in_loop = std::mem::replace(&mut self.is_in_loop, is_in_loop);
+    let last_in_function = std::mem::replace(&mut self.is_in_function, is_in_function);
+    let parsed = parse(self);
+    self.is_in_loop = last_in_loop;
+    self.is_in_function = last_in_function;
+    parsed
+  }
+
+  /// Returns: whether the 'async' modifier is consumed
+  fn consume_async_modifier(&mut self) -> bool {
+    if !Parser::match_current_token_type(&self.current_token, TokenType::Async) {
+      return false;
+    }
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this 'async'
+    true
   }
 
   fn get_single_bare_name_head(
+    &self,
     path_expr_head_token_meta: &ParsingTokenMeta,
   ) -> Option<NamePathHead> {
+    let head_span = self.get_span(path_expr_head_token_meta.pos);
     match path_expr_head_token_meta.kind {
       TokenType::Identifier => Some(NamePathHead::Identifier(Identifier {
         name: path_expr_head_token_meta.raw.clone(),
-        pos: path_expr_head_token_meta.pos,
+        span: head_span,
       })),
-      TokenType::Crate => Some(NamePathHead::CrateSymbol(path_expr_head_token_meta.pos)),
-      TokenType::_self_ => Some(NamePathHead::SelfSymbol(path_expr_head_token_meta.pos)),
-      TokenType::_Self_ => Some(NamePathHead::BigSelfSymbol(path_expr_head_token_meta.pos)),
+      TokenType::Crate => Some(NamePathHead::CrateSymbol(head_span)),
+      TokenType::_self_ => Some(NamePathHead::SelfSymbol(head_span)),
+      TokenType::_Self_ => Some(NamePathHead::BigSelfSymbol(head_span)),
       _ => None,
     }
   }
 
   pub fn new(contents: &'a str) -> Self {
+    Parser::new_in_file(contents, FileId::default())
+  }
+
+  /// The spans of AST nodes are recorded in the given file.
+  pub fn new_in_file(contents: &'a str, file_id: FileId) -> Self {
     let mut new_parser = Self {
       current_token: None,
       last_token: None,
+      file_id,
       lexer: Lexer::new(contents),
       errors: Vec::new(),
+      allow_struct_init: true,
+      is_in_loop: false,
+      is_in_function: false,
+      doc_comments_cursor: 0,
+      rest_array_errors: Vec::new(),
     };
     Parser::move_to_next_token(
       &mut new_parser.lexer,
@@ -171,7 +375,10 @@ impl<'a> Parser<'a> {
             "bool literal true",
           );
           Some(Expression::NormalExpression(
-            NormalExpression::SimpleLiteral(SimpleLiteral::BooleanLiteral(true), lit_token.pos),
+            NormalExpression::SimpleLiteral(
+              SimpleLiteral::BooleanLiteral(true),
+              self.get_span(lit_token.pos),
+            ),
           ))
         }
         TokenType::False => {
@@ -182,7 +389,10 @@ impl<'a> Parser<'a> {
             "bool literal false",
           );
           Some(Expression::NormalExpression(
-            NormalExpression::SimpleLiteral(SimpleLiteral::BooleanLiteral(false), lit_token.pos),
+            NormalExpression::SimpleLiteral(
+              SimpleLiteral::BooleanLiteral(false),
+              self.get_span(lit_token.pos),
+            ),
           ))
         }
         TokenType::DecimalInteger => {
@@ -195,7 +405,7 @@ impl<'a> Parser<'a> {
           Some(Expression::NormalExpression(
             NormalExpression::SimpleLiteral(
               SimpleLiteral::DecimalLiteral(lit_token.raw),
-              lit_token.pos,
+              self.get_span(lit_token.pos),
             ),
           ))
         }
@@ -209,7 +419,7 @@ impl<'a> Parser<'a> {
           Some(Expression::NormalExpression(
             NormalExpression::SimpleLiteral(
               SimpleLiteral::OctalLiteral(lit_token.raw),
-              lit_token.pos,
+              self.get_span(lit_token.pos),
             ),
           ))
         }
@@ -223,7 +433,7 @@ impl<'a> Parser<'a> {
           Some(Expression::NormalExpression(
             NormalExpression::SimpleLiteral(
               SimpleLiteral::HexLiteral(lit_token.raw),
-              lit_token.pos,
+              self.get_span(lit_token.pos),
             ),
           ))
         }
@@ -237,7 +447,7 @@ impl<'a> Parser<'a> {
           Some(Expression::NormalExpression(
             NormalExpression::SimpleLiteral(
               SimpleLiteral::BinaryLiteral(lit_token.raw),
-              lit_token.pos,
+              self.get_span(lit_token.pos),
             ),
           ))
         }
@@ -251,7 +461,7 @@ impl<'a> Parser<'a> {
           Some(Expression::NormalExpression(
             NormalExpression::SimpleLiteral(
               SimpleLiteral::ExponentLiteral(lit_token.raw),
-              lit_token.pos,
+              self.get_span(lit_token.pos),
             ),
           ))
         }
@@ -265,7 +475,7 @@ impl<'a> Parser<'a> {
           Some(Expression::NormalExpression(
             NormalExpression::SimpleLiteral(
               SimpleLiteral::FloatLiteral(lit_token.raw),
-              lit_token.pos,
+              self.get_span(lit_token.pos),
             ),
           ))
         }
@@ -279,7 +489,7 @@ impl<'a> Parser<'a> {
           Some(Expression::NormalExpression(
             NormalExpression::SimpleLiteral(
               SimpleLiteral::CharLiteral(lit_token.raw),
-              lit_token.pos,
+              self.get_span(lit_token.pos),
             ),
           ))
         }
@@ -293,7 +503,7 @@ impl<'a> Parser<'a> {
           Some(Expression::NormalExpression(
             NormalExpression::SimpleLiteral(
               SimpleLiteral::StringLiteral(lit_token.raw),
-              lit_token.pos,
+              self.get_span(lit_token.pos),
             ),
           ))
         }
@@ -313,9 +523,10 @@ impl<'a> Parser<'a> {
       &mut self.current_token,
       "left parenthesis",
     ); // moves over this '('
-    if let Some(expression) = self.parse_expression() {
+    let errors_count = self.errors.len();
+    if let Some(expression) = self.with_struct_init_allowed(true, Parser::parse_expression) {
       if Parser::match_current_token_type(&mut self.current_token, TokenType::RightParen) {
-        let right_paren_token = Parser::get_current_token_meta_and_move_next(
+        Parser::get_current_token_meta_and_move_next(
           &mut self.lexer,
           &mut self.last_token,
           &mut self.current_token,
@@ -323,8 +534,7 @@ impl<'a> Parser<'a> {
         ); // moves over this ')'
         return Some(Expression::NormalExpression(NormalExpression::Grouping(
           Box::new(expression),
-          left_paren_token.pos,
-          right_paren_token.pos,
+          self.get_span_from(left_paren_token.pos.start),
         )));
       } else {
         // error: expected right parenthesis to close this grouping expression
@@ -332,7 +542,7 @@ impl<'a> Parser<'a> {
           CompileError::ExpectedRightParenthesisAfterExpression { pos }
         });
       }
-    } else {
+    } else if self.errors.len() == errors_count {
       // error: expected expression after left parenthesis
       self
         .errors
@@ -354,7 +564,13 @@ impl<'a> Parser<'a> {
       "left bracket",
     ); // moves over this '['
     let mut expr_list: Vec<Expression> = vec![];
-    while let Some(expr) = self.parse_expression() {
+    loop {
+      if Parser::match_current_token_type(&self.current_token, TokenType::ThreeDots) {
+        return self.parse_array_literal_rest(left_bracket_token.pos.start, expr_list);
+      }
+      let Some(expr) = self.with_struct_init_allowed(true, Parser::parse_expression) else {
+        break;
+      };
       expr_list.push(expr);
       if Parser::match_current_token_type(&mut self.current_token, TokenType::Comma) {
         Parser::move_to_next_token(
@@ -363,17 +579,16 @@ impl<'a> Parser<'a> {
           &mut self.current_token,
         ); // moves over this ','
       } else if Parser::match_current_token_type(&mut self.current_token, TokenType::RightBracket) {
-        let right_bracket_token = Parser::get_current_token_meta_and_move_next(
+        Parser::move_to_next_token(
           &mut self.lexer,
           &mut self.last_token,
           &mut self.current_token,
-          "right bracket",
         ); // moves over this ']'
         return Some(Expression::NormalExpression(
           NormalExpression::ArrayLiteral(
             expr_list,
-            left_bracket_token.pos,
-            right_bracket_token.pos,
+            None,
+            self.get_span_from(left_bracket_token.pos.start),
           ),
         ));
       } else {
@@ -387,6 +602,42 @@ impl<'a> Parser<'a> {
     None
   }
 
+  /// The rest element of an array literal and the closing bracket,
+  /// the elements before it have been parsed. <br>
+  /// Examples: `...rest]`, `...[n4, ...rest]]`
+  fn parse_array_literal_rest(
+    &mut self,
+    start: usize,
+    expr_list: Vec<Expression>,
+  ) -> Option<Expression> {
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this '...'
+    let rest = self.with_struct_init_allowed(true, Parser::parse_expression_range)?;
+    if let NormalExpression::ArrayLiteral(_, Some(_), _) = rest {
+      // the nested array is checked along with this one
+      self.rest_array_errors.pop();
+    }
+    self.consume_expected_token(
+      TokenType::RightBracket,
+      "right bracket ']' after the rest element",
+    )?;
+    // only an array destructing can have a rest element,
+    // the error is dropped once the array turns out to be assigned to
+    if let Some(current_pos) = Parser::get_token_pos(&self.current_token) {
+      self.rest_array_errors.push(CompileError::ExpectedToken {
+        expected: String::from("equal sign '=' after array destructing"),
+        found: Parser::get_token_display_name(&self.current_token),
+        pos: current_pos,
+      });
+    }
+    Some(Expression::NormalExpression(
+      NormalExpression::ArrayLiteral(expr_list, Some(Box::new(rest)), self.get_span_from(start)),
+    ))
+  }
+
   pub fn parse_expression_name_path_expression(&mut self) -> Option<Expression> {
     if !Parser::match_current_token_types(
       &self.current_token,
@@ -405,12 +656,12 @@ impl<'a> Parser<'a> {
       &mut self.current_token,
       "path expression head",
     ); // moves over this path expression head
-    let name_head = Parser::get_single_bare_name_head(&path_expr_head_token_meta)
-      .expect(nebula_interal_err("expected creating name header struct but failed").as_str());
+    let name_head = self.get_single_bare_name_head(&path_expr_head_token_meta)?;
     if !Parser::match_current_token_type(&self.current_token, TokenType::DoubleColon) {
       // Only single bare head here: Identifier, 'crate', 'self' or 'Self'
       return Some(Expression::NormalExpression(
         NormalExpression::NamePathExpression(NamePathExpression {
+          span: name_head.span(),
           head: name_head,
           suffix: None,
         }),
@@ -430,10 +681,7 @@ impl<'a> Parser<'a> {
           &mut self.current_token,
           "path expression head",
         );
-        suffix.push(Identifier {
-          name: identifier_token.raw,
-          pos: identifier_token.pos,
-        });
+        suffix.push(self.get_identifier(identifier_token));
       } else {
         // error: Expected an identifier after double colon in name path expression
         Parser::collect_err_on_current_token_pos(&mut self.errors, &self.current_token, |pos| {
@@ -446,20 +694,1953 @@ impl<'a> Parser<'a> {
       NormalExpression::NamePathExpression(NamePathExpression {
         head: name_head,
         suffix: Some(suffix),
+        span: self.get_span_from(path_expr_head_token_meta.pos.start),
       }),
     ))
   }
 
-  pub fn parse_expression(&mut self) -> Option<Expression> {
-    if let Some(grouping_expr) = self.parse_expression_grouping() {
-      return Some(grouping_expr);
+  /// Operator-precedence table for binary operators, following `specs/NebulaParser.g4`. <br>
+  /// Returns: binary operator, precedence (greater binds tighter), is right associative
+  fn get_binary_operator_info(
+    ref_current_token: &Option<Token>,
+  ) -> Option<(BinaryOperator, u8, bool)> {
+    let token_kind = Parser::get_token_kind(ref_current_token)?;
+    match token_kind {
+      TokenType::DoubleStar => Some((BinaryOperator::Exponent, 10, true)),
+      TokenType::Star => Some((BinaryOperator::Multiplication, 9, false)),
+      TokenType::Slash => Some((BinaryOperator::Division, 9, false)),
+      TokenType::Percent => Some((BinaryOperator::Modulo, 9, false)),
+      TokenType::Plus => Some((BinaryOperator::Addition, 8, false)),
+      TokenType::Minus => Some((BinaryOperator::Subtraction, 8, false)),
+      TokenType::DoubleLeftAngle => Some((BinaryOperator::BitwiseShiftLeft, 7, false)),
+      TokenType::DoubleRightAngle => Some((BinaryOperator::BitwiseShiftRight, 7, false)),
+      TokenType::Ampersand => Some((BinaryOperator::BitwiseAnd, 6, false)),
+      TokenType::Caret => Some((BinaryOperator::BitwiseXor, 5, false)),
+      TokenType::Vertical => Some((BinaryOperator::BitwiseOr, 4, false)),
+      TokenType::DoubleEqual => Some((BinaryOperator::Equals, 3, false)),
+      TokenType::BangEqual => Some((BinaryOperator::NotEquals, 3, false)),
+      TokenType::LeftAngle => Some((BinaryOperator::LessThan, 3, false)),
+      TokenType::LeftAngleEqual => Some((BinaryOperator::LessThanOrEquals, 3, false)),
+      TokenType::RightAngle => Some((BinaryOperator::GreaterThan, 3, false)),
+      TokenType::RightAngleEqual => Some((BinaryOperator::GreaterThanOrEquals, 3, false)),
+      TokenType::DoubleAmpersand => Some((BinaryOperator::LogicalAnd, 2, false)),
+      TokenType::DoubleVertical => Some((BinaryOperator::LogicalOr, 1, false)),
+      _ => None,
+    }
+  }
+
+  /// Returns: `None` for the plain assignment `=`
+  fn get_compound_assignment_operator(
+    ref_current_token: &Option<Token>,
+  ) -> Option<CompoundAssignmentOperator> {
+    let token_kind = Parser::get_token_kind(ref_current_token)?;
+    match token_kind {
+      TokenType::PlusEqual => Some(CompoundAssignmentOperator::Addition),
+      TokenType::MinusEqual => Some(CompoundAssignmentOperator::Subtraction),
+      TokenType::StarEqual => Some(CompoundAssignmentOperator::Multiplication),
+      TokenType::SlashEqual => Some(CompoundAssignmentOperator::Division),
+      TokenType::PercentEqual => Some(CompoundAssignmentOperator::Modulo),
+      TokenType::DoubleStarEqual => Some(CompoundAssignmentOperator::Exponent),
+      TokenType::AmpersandEqual => Some(CompoundAssignmentOperator::BitwiseAnd),
+      TokenType::VerticalEqual => Some(CompoundAssignmentOperator::BitwiseOr),
+      TokenType::CaretEqual => Some(CompoundAssignmentOperator::BitwiseXor),
+      TokenType::DoubleLeftAngleEqual => Some(CompoundAssignmentOperator::BitwiseShiftLeft),
+      TokenType::DoubleRightAngleEqual => Some(CompoundAssignmentOperator::BitwiseShiftRight),
+      TokenType::DoubleAmpersandEqual => Some(CompoundAssignmentOperator::LogicalAnd),
+      TokenType::DoubleVerticalEqual => Some(CompoundAssignmentOperator::LogicalOr),
+      _ => None,
+    }
+  }
+
+  fn match_assignment_operator(ref_current_token: &Option<Token>) -> bool {
+    Parser::match_current_token_type(ref_current_token, TokenType::Equal)
+      || Parser::get_compound_assignment_operator(ref_current_token).is_some()
+  }
+
+  /// Fields initializing of a struct init expression, the struct name has been consumed. <br>
+  /// Examples: `{ name = "John", age = 21, }`
+  fn parse_struct_init_fields(&mut self) -> Option<Vec<(Identifier, Expression)>> {
+    self.consume_expected_token(TokenType::LeftBrace, "left brace '{' to init struct")?;
+    let mut fields: Vec<(Identifier, Expression)> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      let field_name = self.consume_identifier("a field name to init")?;
+      let equal_token =
+        self.consume_expected_token(TokenType::Equal, "equal sign '=' after field name")?;
+      let errors_count = self.errors.len();
+      let field_value = self.with_struct_init_allowed(true, Parser::parse_expression);
+      match field_value {
+        Some(field_value) => fields.push((field_name, field_value)),
+        None => {
+          if self.errors.len() == errors_count {
+            // error: expected the initial value of field
+            self
+              .errors
+              .push(CompileError::ExpectedExpressionAfterOperator {
+                op_str: equal_token.raw,
+                pos: equal_token.pos,
+              });
+          }
+          return None;
+        }
+      }
+      if !Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        break;
+      }
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ','
+    }
+    self.consume_expected_token(
+      TokenType::RightBrace,
+      "a comma ',' or right brace '}' after field value",
+    )?;
+    Some(fields)
+  }
+
+  /// Examples: `new People`
+  pub fn parse_expression_new(&mut self) -> Option<NormalExpression> {
+    let new_token = self.consume_expected_token(TokenType::New, "keyword 'new'")?;
+    let struct_name = self.consume_identifier("a struct name after 'new'")?;
+    Some(NormalExpression::NewExpression(
+      struct_name,
+      self.get_span_from(new_token.pos.start),
+    ))
+  }
+
+  /// The body is a single expression or a block, the commas before `->` belong to the
+  /// parameters. A comma after the body ends the lambda, so it belongs to the outer list,
+  /// such as the call arguments in `reduce($: acc, x -> acc + x, 0)`. <br>
+  /// Examples: `$: f, i -> f + i`, `$: letter -> { letter.to_lower_case() }`,
+  /// `async $: ...urls -> { }` <br>
+  /// The 'async' modifier has been consumed by caller if exists, the lambda starts at `start`.
+  pub fn parse_expression_lambda(
+    &mut self,
+    start: usize,
+    is_async: bool,
+  ) -> Option<NormalExpression> {
+    self.consume_expected_token(TokenType::DollarColon, "lambda label '$:'")?;
+    let (params, rest_param) = self.parse_param_list(TokenType::RightArrow)?;
+    let arrow_token = self.consume_expected_token(
+      TokenType::RightArrow,
+      "a comma ',' or right arrow '->' after lambda parameter",
+    )?;
+    let body =
+      self.with_body_context(false, true, |parser| parser.parse_lambda_body(arrow_token))?;
+    Some(NormalExpression::LambdaExpression(LambdaExpression {
+      is_async,
+      params,
+      rest_param,
+      body,
+      span: self.get_span_from(start),
+    }))
+  }
+
+  fn parse_lambda_body(&mut self, arrow_token: ParsingTokenMeta) -> Option<Vec<Statement>> {
+    if Parser::match_current_token_type(&self.current_token, TokenType::LeftBrace) {
+      return self.parse_block();
+    }
+    let errors_count = self.errors.len();
+    let Some(body_expr) = self.parse_expression() else {
+      if self.errors.len() == errors_count {
+        // error: expected the body of lambda
+        self
+          .errors
+          .push(CompileError::ExpectedExpressionAfterOperator {
+            op_str: arrow_token.raw,
+            pos: arrow_token.pos,
+          });
+      }
+      return None;
+    };
+    Some(vec![Statement::TrailingExpression(body_expr)])
+  }
+
+  /// The atoms of normal expressions, which can be used as operands of operators.
+  pub fn parse_expression_primary(&mut self) -> Option<NormalExpression> {
+    if Parser::match_current_token_type(&self.current_token, TokenType::New) {
+      return self.parse_expression_new();
+    }
+    if Parser::match_current_token_types(
+      &self.current_token,
+      vec![TokenType::Async, TokenType::DollarColon],
+    ) {
+      let start = self.get_current_start();
+      let is_async = self.consume_async_modifier();
+      return self.parse_expression_lambda(start, is_async);
+    }
+    if Parser::match_current_token_type(&self.current_token, TokenType::Struct) {
+      // anonymous struct init
+      let struct_token = Parser::get_current_token_meta_and_move_next(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+        "keyword 'struct'",
+      ); // moves over this 'struct'
+      let fields = self.parse_struct_init_fields()?;
+      return Some(NormalExpression::StructInitExpression(
+        None,
+        fields,
+        self.get_span_from(struct_token.pos.start),
+      ));
+    }
+    let primary_expr =
+      if Parser::match_current_token_type(&self.current_token, TokenType::LeftParen) {
+        self.parse_expression_grouping()
+      } else if Parser::match_current_token_type(&self.current_token, TokenType::LeftBracket) {
+        self.parse_expression_array_literal()
+      } else if let Some(simple_literal) = self.parse_expression_simple_literal() {
+        Some(simple_literal)
+      } else {
+        self.parse_expression_name_path_expression()
+      };
+    match primary_expr {
+      Some(Expression::NormalExpression(NormalExpression::NamePathExpression(
+        NamePathExpression {
+          head: NamePathHead::Identifier(struct_name),
+          suffix: None,
+          ..
+        },
+      )))
+        if self.allow_struct_init
+          && Parser::match_current_token_type(&self.current_token, TokenType::LeftBrace) =>
+      {
+        let fields = self.parse_struct_init_fields()?;
+        let span = self.get_span_from(struct_name.span.start);
+        Some(NormalExpression::StructInitExpression(
+          Some(struct_name),
+          fields,
+          span,
+        ))
+      }
+      Some(Expression::NormalExpression(normal_expr)) => Some(normal_expr),
+      _ => None,
+    }
+  }
+
+  /// Arguments list of a call expression, the left parenthesis '(' has been consumed,
+  /// and the right parenthesis ')' will be consumed here.
+  fn parse_call_arguments(&mut self) -> Option<Vec<Expression>> {
+    let mut arguments: Vec<Expression> = vec![];
+    if !Parser::match_current_token_type(&self.current_token, TokenType::RightParen) {
+      loop {
+        let errors_count = self.errors.len();
+        if let Some(argument) = self.parse_expression() {
+          arguments.push(argument);
+        } else {
+          if self.errors.len() == errors_count {
+            // error: expected an argument expression
+            Parser::collect_err_on_current_token_pos(
+              &mut self.errors,
+              &self.current_token,
+              |pos| CompileError::ExpectedCommaOrRightParenthesisAfterArgument { pos },
+            );
+          }
+          return None;
+        }
+        if Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+          Parser::move_to_next_token(
+            &mut self.lexer,
+            &mut self.last_token,
+            &mut self.current_token,
+          ); // moves over this ','
+        } else if Parser::match_current_token_type(&self.current_token, TokenType::RightParen) {
+          break;
+        } else {
+          // error: call arguments list is not closed
+          Parser::collect_err_on_current_token_pos(&mut self.errors, &self.current_token, |pos| {
+            CompileError::ExpectedCommaOrRightParenthesisAfterArgument { pos }
+          });
+          return None;
+        }
+      }
+    }
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this ')'
+    Some(arguments)
+  }
+
+  /// Postfix chains: call, index, member access and optional chaining. <br>
+  /// Examples: `a.b?.c(1, 2)[i]`
+  pub fn parse_expression_postfix(&mut self) -> Option<NormalExpression> {
+    let mut source = self.parse_expression_primary()?;
+    let start = source.span().start;
+    while let Some(token_kind) = Parser::get_token_kind(&self.current_token) {
+      match token_kind {
+        TokenType::Dot | TokenType::QuestionDot => {
+          let dot_token = Parser::get_current_token_meta_and_move_next(
+            &mut self.lexer,
+            &mut self.last_token,
+            &mut self.current_token,
+            "dot or question dot",
+          ); // moves over this '.' or '?.'
+          if !Parser::match_current_token_type(&self.current_token, TokenType::Identifier) {
+            // error: expected a field name after '.' or '?.'
+            self.errors.push(CompileError::ExpectedIdentifierAfterDot {
+              dot_str: dot_token.raw,
+              pos: dot_token.pos,
+            });
+            return None;
+          }
+          let field_token = Parser::get_current_token_meta_and_move_next(
+            &mut self.lexer,
+            &mut self.last_token,
+            &mut self.current_token,
+            "field name",
+          ); // moves over this field name
+          source = NormalExpression::GetExpression(
+            Box::new(source),
+            self.get_identifier(field_token),
+            dot_token.kind == TokenType::QuestionDot,
+            self.get_span_from(start),
+          );
+        }
+        TokenType::LeftParen => {
+          Parser::move_to_next_token(
+            &mut self.lexer,
+            &mut self.last_token,
+            &mut self.current_token,
+          ); // moves over this '('
+          let arguments = self.with_struct_init_allowed(true, Parser::parse_call_arguments)?;
+          source = NormalExpression::CallExpression(
+            Box::new(source),
+            arguments,
+            self.get_span_from(start),
+          );
+        }
+        TokenType::LeftBracket => {
+          let left_bracket_token = Parser::get_current_token_meta_and_move_next(
+            &mut self.lexer,
+            &mut self.last_token,
+            &mut self.current_token,
+            "left bracket",
+          ); // moves over this '['
+          let errors_count = self.errors.len();
+          let index = match self.with_struct_init_allowed(true, Parser::parse_expression) {
+            Some(index) => index,
+            None => {
+              if self.errors.len() == errors_count {
+                // error: expected an index expression
+                self
+                  .errors
+                  .push(CompileError::ExpectedExpressionAfterLeftBracket {
+                    pos: left_bracket_token.pos,
+                  });
+              }
+              return None;
+            }
+          };
+          if !Parser::match_current_token_type(&self.current_token, TokenType::RightBracket) {
+            // error: expected right bracket to close this index expression
+            Parser::collect_err_on_current_token_pos(
+              &mut self.errors,
+              &self.current_token,
+              |pos| CompileError::ExpectedRightBracketAfterIndex { pos },
+            );
+            return None;
+          }
+          Parser::move_to_next_token(
+            &mut self.lexer,
+            &mut self.last_token,
+            &mut self.current_token,
+          ); // moves over this ']'
+          source = NormalExpression::IndexExpression(
+            Box::new(source),
+            Box::new(index),
+            self.get_span_from(start),
+          );
+        }
+        _ => break,
+      }
+    }
+    Some(source)
+  }
+
+  /// Unary operators (`-`, `!`) bind tighter than any binary operator, including `**`.
+  pub fn parse_expression_unary(&mut self) -> Option<NormalExpression> {
+    let unary_operator = match Parser::get_token_kind(&self.current_token) {
+      Some(TokenType::Minus) => UnaryOperator::Negation,
+      Some(TokenType::Bang) => UnaryOperator::Not,
+      _ => return self.parse_expression_postfix(),
+    };
+    let operator_token = Parser::get_current_token_meta_and_move_next(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+      "unary operator",
+    ); // moves over this '-' or '!'
+    let errors_count = self.errors.len();
+    if let Some(operand) = self.parse_expression_unary() {
+      return Some(NormalExpression::UnaryExpression(
+        Box::new(operand),
+        unary_operator,
+        self.get_span_from(operator_token.pos.start),
+      ));
+    }
+    if self.errors.len() == errors_count {
+      // error: expected an operand after unary operator
+      self
+        .errors
+        .push(CompileError::ExpectedExpressionAfterOperator {
+          op_str: operator_token.raw,
+          pos: operator_token.pos,
+        });
+    }
+    None
+  }
+
+  /// Precedence climbing for binary operators.
+  /// Only operators whose precedence is not less than `min_precedence` will be consumed here.
+  pub fn parse_expression_binary(&mut self, min_precedence: u8) -> Option<NormalExpression> {
+    let mut left_hand = self.parse_expression_unary()?;
+    while let Some((binary_operator, precedence, is_right_assoc)) =
+      Parser::get_binary_operator_info(&self.current_token)
+    {
+      if precedence < min_precedence {
+        break;
+      }
+      let operator_token = Parser::get_current_token_meta_and_move_next(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+        "binary operator",
+      ); // moves over this binary operator
+      let next_min_precedence = if is_right_assoc {
+        precedence
+      } else {
+        precedence + 1
+      };
+      let errors_count = self.errors.len();
+      if let Some(right_hand) = self.parse_expression_binary(next_min_precedence) {
+        let span = left_hand.span().to(right_hand.span());
+        left_hand = NormalExpression::BinaryExpression(
+          Box::new(left_hand),
+          binary_operator,
+          Box::new(right_hand),
+          span,
+        );
+      } else {
+        if self.errors.len() == errors_count {
+          // error: expected an operand after binary operator
+          self
+            .errors
+            .push(CompileError::ExpectedExpressionAfterOperator {
+              op_str: operator_token.raw,
+              pos: operator_token.pos,
+            });
+        }
+        return None;
+      }
+    }
+    Some(left_hand)
+  }
+
+  /// Range expression has the lowest precedence among normal expressions,
+  /// and it's not associative: `1..2..3` is not allowed.
+  pub fn parse_expression_range(&mut self) -> Option<NormalExpression> {
+    let range_start = self.parse_expression_binary(0)?;
+    if !Parser::match_current_token_types(
+      &self.current_token,
+      vec![TokenType::DoubleDots, TokenType::DoubleDotsEqual],
+    ) {
+      return Some(range_start);
+    }
+    let operator_token = Parser::get_current_token_meta_and_move_next(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+      "range operator",
+    ); // moves over this '..' or '..='
+    let errors_count = self.errors.len();
+    if let Some(range_end) = self.parse_expression_binary(0) {
+      let span = range_start.span().to(range_end.span());
+      return Some(NormalExpression::RangeExpression(
+        Box::new(range_start),
+        Box::new(range_end),
+        operator_token.kind == TokenType::DoubleDotsEqual,
+        span,
+      ));
+    }
+    if self.errors.len() == errors_count {
+      // error: expected the end of range
+      self
+        .errors
+        .push(CompileError::ExpectedExpressionAfterOperator {
+          op_str: operator_token.raw,
+          pos: operator_token.pos,
+        });
+    }
+    None
+  }
+
+  /// Returns the variable name if the expression is a single identifier, such as `a`.
+  fn get_bare_identifier(expr: Expression) -> Option<Identifier> {
+    match expr {
+      Expression::NormalExpression(NormalExpression::NamePathExpression(NamePathExpression {
+        head: NamePathHead::Identifier(identifier),
+        suffix: None,
+        ..
+      })) => Some(identifier),
+      _ => None,
+    }
+  }
+
+  /// Whether any link of the receiver chain is optional, such as `a?.b` in `a?.b[0].c`.
+  fn is_in_optional_chain(expr: &NormalExpression) -> bool {
+    match expr {
+      NormalExpression::GetExpression(_, _, true, _) => true,
+      NormalExpression::GetExpression(receiver, ..)
+      | NormalExpression::IndexExpression(receiver, ..)
+      | NormalExpression::CallExpression(receiver, ..) => Parser::is_in_optional_chain(receiver),
+      _ => false,
+    }
+  }
+
+  /// Turns an array literal into the array destructing assigned to,
+  /// the elements must be variables and the rest element can be another array. <br>
+  /// Examples: `[a, b]`, `[n1, n2, ...[n3, ...rest]]`
+  fn get_array_destruct(
+    expr_list: Vec<Expression>,
+    rest: Option<Box<NormalExpression>>,
+    span: Span,
+  ) -> Option<ArrayDestructAssign> {
+    let vars = expr_list
+      .into_iter()
+      .map(Parser::get_bare_identifier)
+      .collect::<Option<Vec<Identifier>>>()?;
+    let rest = match rest.map(|rest| *rest) {
+      None => None,
+      Some(NormalExpression::ArrayLiteral(child_list, child_rest, child_span)) => {
+        Some(ArrayDestructRest::ChildRest(Box::new(
+          Parser::get_array_destruct(child_list, child_rest, child_span)?,
+        )))
+      }
+      Some(rest) => Some(ArrayDestructRest::Identifier(Parser::get_bare_identifier(
+        Expression::NormalExpression(rest),
+      )?)),
+    };
+    Some(ArrayDestructAssign { vars, rest, span })
+  }
+
+  /// Validates the left-hand side of an assignment, optional chains like `a?.b` or `a?.b[0]`
+  /// and other expressions like `f()` can't be assigned to.
+  fn get_assignment_left_hand(
+    left_hand: NormalExpression,
+    is_compound: bool,
+  ) -> Option<AssignmentLeftHand> {
+    if Parser::is_in_optional_chain(&left_hand) {
+      return None;
+    }
+    match left_hand {
+      NormalExpression::GetExpression(..) => Some(AssignmentLeftHand::GetExpression(Box::new(
+        Expression::NormalExpression(left_hand),
+      ))),
+      NormalExpression::IndexExpression(..) => Some(AssignmentLeftHand::IndexExpression(Box::new(
+        Expression::NormalExpression(left_hand),
+      ))),
+      // destructing is only for the plain assignment
+      NormalExpression::ArrayLiteral(expr_list, rest, span) if !is_compound => {
+        Parser::get_array_destruct(expr_list, rest, span).map(AssignmentLeftHand::Destruct)
+      }
+      _ => Parser::get_bare_identifier(Expression::NormalExpression(left_hand))
+        .map(AssignmentLeftHand::Identifier),
+    }
+  }
+
+  /// The right-hand side of an assignment, the assignment operator has been consumed.
+  fn parse_assignment_value(&mut self, operator_token: &ParsingTokenMeta) -> Option<Expression> {
+    let errors_count = self.errors.len();
+    let value = self.parse_expression();
+    if value.is_none() && self.errors.len() == errors_count {
+      // error: expected the value to assign
+      self
+        .errors
+        .push(CompileError::ExpectedExpressionAfterOperator {
+          op_str: operator_token.raw.clone(),
+          pos: operator_token.pos,
+        });
+    }
+    value
+  }
+
+  /// Assignments are right associative: `a = b = 1` is `a = (b = 1)`. <br>
+  /// Examples: `a = 1`, `a.b += 1`, `arr[0] **= 2`, `[a, b] = [b, a]`
+  pub fn parse_expression_assignment(&mut self) -> Option<NormalExpression> {
+    let rest_arrays_count = self.rest_array_errors.len();
+    let Some(left_hand) = self.parse_expression_range() else {
+      self.rest_array_errors.truncate(rest_arrays_count);
+      return None;
+    };
+    if !Parser::match_assignment_operator(&self.current_token) {
+      self.collect_rest_array_errors(rest_arrays_count);
+      return Some(left_hand);
+    }
+    let start = left_hand.span().start;
+    let compound_operator = Parser::get_compound_assignment_operator(&self.current_token);
+    let operator_token = Parser::get_current_token_meta_and_move_next(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+      "assignment operator",
+    ); // moves over this '=' or compound assignment operator
+    let left_hand = Parser::get_assignment_left_hand(left_hand, compound_operator.is_some());
+    match left_hand {
+      // the rest elements are destructed into, or reported along with the whole left-hand side
+      Some(AssignmentLeftHand::Destruct(_)) | None => {
+        self.rest_array_errors.truncate(rest_arrays_count)
+      }
+      Some(_) => self.collect_rest_array_errors(rest_arrays_count),
     }
-    if let Some(simple_literal) = self.parse_expression_simple_literal() {
-      return Some(simple_literal);
+    let Some(left_hand) = left_hand else {
+      // error: the left-hand side can't be assigned to
+      self.errors.push(CompileError::InvalidAssignmentTarget {
+        pos: operator_token.pos,
+      });
+      return None;
+    };
+    // parses the right-hand side recursively for right associativity
+    let value = Box::new(self.parse_assignment_value(&operator_token)?);
+    let span = self.get_span_from(start);
+    Some(match compound_operator {
+      Some(compound_operator) => {
+        NormalExpression::CompoundAssignmentExpression(left_hand, compound_operator, value, span)
+      }
+      None => NormalExpression::AssignmentExpression(left_hand, value, span),
+    })
+  }
+
+  /// error: the array literals with a rest element since the given count are not assigned to
+  fn collect_rest_array_errors(&mut self, rest_arrays_count: usize) {
+    let rest_array_errors = self.rest_array_errors.split_off(rest_arrays_count);
+    self.errors.extend(rest_array_errors);
+  }
+
+  pub fn parse_normal_expression(&mut self) -> Option<NormalExpression> {
+    self.parse_expression_assignment()
+  }
+
+  /// Normal expression as a condition or iterable of with-block-expressions.
+  fn parse_head_expression(&mut self, expected_desc: &str) -> Option<Box<Expression>> {
+    let errors_count = self.errors.len();
+    if let Some(head_expr) = self.with_struct_init_allowed(false, Parser::parse_normal_expression) {
+      return Some(Box::new(Expression::NormalExpression(head_expr)));
     }
-    if let Some(array_literal) = self.parse_expression_array_literal() {
-      return Some(array_literal);
+    if self.errors.len() == errors_count {
+      // error: missing condition or iterable
+      self.collect_expected_err_on_current_token(expected_desc);
     }
     None
   }
+
+  /// Examples: `{ var a = 1; a + 1 }`
+  fn parse_bare_block(&mut self) -> Option<ExpressionWithBlock> {
+    let start = self.get_current_start();
+    let statements = self.parse_block()?;
+    Some(ExpressionWithBlock::BareBlock(
+      statements,
+      self.get_span_from(start),
+    ))
+  }
+
+  /// A block used as the body of with-block-expressions,
+  /// see the tips of `ExpressionWithBlock` for its structure.
+  fn parse_block_body(&mut self) -> Option<Box<Statement>> {
+    let block = self.parse_bare_block()?;
+    let span = block.span();
+    Some(Box::new(Statement::ExpressionStatement(
+      Expression::ExpressionWithBlock(block),
+      span,
+    )))
+  }
+
+  /// `break` and `continue` are allowed in the body of loops.
+  fn parse_loop_body(&mut self) -> Option<Box<Statement>> {
+    let is_in_function = self.is_in_function;
+    self.with_body_context(true, is_in_function, Parser::parse_block_body)
+  }
+
+  /// Examples: `loop { ... }`
+  pub fn parse_expression_loop(&mut self) -> Option<ExpressionWithBlock> {
+    let loop_token = self.consume_expected_token(TokenType::Loop, "keyword 'loop'")?;
+    let block = self.parse_loop_body()?;
+    Some(ExpressionWithBlock::LoopExpression {
+      block,
+      span: self.get_span_from(loop_token.pos.start),
+    })
+  }
+
+  /// Examples: `while i < 3 { ... }`
+  pub fn parse_expression_while(&mut self) -> Option<ExpressionWithBlock> {
+    let while_token = self.consume_expected_token(TokenType::While, "keyword 'while'")?;
+    let condition = self.parse_head_expression("a condition expression after 'while'")?;
+    let block = self.parse_loop_body()?;
+    Some(ExpressionWithBlock::WhileExpression {
+      condition,
+      block,
+      span: self.get_span_from(while_token.pos.start),
+    })
+  }
+
+  fn parse_for_loop_alias(&mut self) -> Option<ForLoopAlias> {
+    let alias = self.consume_identifier("an alias name or '_' in for loop")?;
+    if alias.name == "_" {
+      Some(ForLoopAlias::Anonymous(alias.span))
+    } else {
+      Some(ForLoopAlias::Named(alias))
+    }
+  }
+
+  /// Examples: `for n in 0..10 { ... }`, `for i, _ in arr { ... }`
+  pub fn parse_expression_for_each(&mut self) -> Option<ExpressionWithBlock> {
+    let for_token = self.consume_expected_token(TokenType::For, "keyword 'for'")?;
+    let mut index_var: Option<ForLoopAlias> = None;
+    let mut element_var = self.parse_for_loop_alias()?;
+    if Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ','
+      index_var = Some(element_var);
+      element_var = self.parse_for_loop_alias()?;
+    }
+    self.consume_expected_token(TokenType::In, "keyword 'in' after for loop alias")?;
+    let iterable = self.parse_head_expression("an iterable expression after 'in'")?;
+    let block = self.parse_loop_body()?;
+    Some(ExpressionWithBlock::ForEachExpression {
+      index_var,
+      element_var,
+      iterable,
+      block,
+      span: self.get_span_from(for_token.pos.start),
+    })
+  }
+
+  /// Examples: `if a > b { a } else if a < b { b } else { 0 }`
+  pub fn parse_expression_if(&mut self) -> Option<ExpressionWithBlock> {
+    let if_token = self.consume_expected_token(TokenType::If, "keyword 'if'")?;
+    let condition = self.parse_head_expression("a condition expression after 'if'")?;
+    let then_block = self.parse_block_body()?;
+    let mut else_if: Vec<(Box<Expression>, Box<Statement>)> = vec![];
+    let mut else_block: Option<Box<Statement>> = None;
+    while Parser::match_current_token_type(&self.current_token, TokenType::Else) {
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this 'else'
+      if Parser::match_current_token_type(&self.current_token, TokenType::If) {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this 'if'
+        let else_if_condition = self.parse_head_expression("a condition expression after 'if'")?;
+        let else_if_block = self.parse_block_body()?;
+        else_if.push((else_if_condition, else_if_block));
+      } else {
+        else_block = Some(self.parse_block_body()?);
+        break;
+      }
+    }
+    Some(ExpressionWithBlock::IfExpression {
+      condition,
+      then_block,
+      else_if,
+      else_block,
+      span: self.get_span_from(if_token.pos.start),
+    })
+  }
+
+  /// A literal, an identifier or an enum path in match arm. <br>
+  /// Examples: `10.01`, `'ç'`, `other`, `HttpStatus::NotFound`
+  fn parse_match_single_arm(&mut self) -> Option<MatchSingleArm> {
+    if let Some(Expression::NormalExpression(NormalExpression::SimpleLiteral(literal, span))) =
+      self.parse_expression_simple_literal()
+    {
+      return Some(MatchSingleArm::Literal(literal, span));
+    }
+    let errors_count = self.errors.len();
+    match self.parse_expression_name_path_expression() {
+      Some(Expression::NormalExpression(NormalExpression::NamePathExpression(
+        NamePathExpression {
+          head: NamePathHead::Identifier(identifier),
+          suffix: None,
+          ..
+        },
+      ))) => Some(MatchSingleArm::Identifier(identifier)),
+      Some(Expression::NormalExpression(NormalExpression::NamePathExpression(name_path))) => {
+        Some(MatchSingleArm::Path(name_path))
+      }
+      _ => {
+        if self.errors.len() == errors_count {
+          // error: not a valid pattern
+          self.collect_expected_err_on_current_token("a match pattern");
+        }
+        None
+      }
+    }
+  }
+
+  /// Examples: `_`, `1..3`, `5..=6`, `8 | 9`, `Exception::NetworkIssue`
+  fn parse_match_arm_pattern(&mut self) -> Option<MatchArmPattern> {
+    if let Some(Token { kind, raw, .. }) = &self.current_token {
+      if *kind == TokenType::Identifier && raw == "_" {
+        let fallback_token = Parser::get_current_token_meta_and_move_next(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+          "fallback pattern",
+        ); // moves over this '_'
+        return Some(MatchArmPattern::Fallback(self.get_span(fallback_token.pos)));
+      }
+    }
+    let first_arm = self.parse_match_single_arm()?;
+    if let MatchSingleArm::Literal(start_literal, start_span) = &first_arm {
+      if Parser::match_current_token_types(
+        &self.current_token,
+        vec![TokenType::DoubleDots, TokenType::DoubleDotsEqual],
+      ) {
+        let range_token = Parser::get_current_token_meta_and_move_next(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+          "range operator",
+        ); // moves over this '..' or '..='
+        if let Some(MatchSingleArm::Literal(end_literal, end_span)) = self.parse_match_single_arm()
+        {
+          return Some(MatchArmPattern::RangePattern(
+            Box::new(NormalExpression::SimpleLiteral(
+              start_literal.clone(),
+              *start_span,
+            )),
+            Box::new(NormalExpression::SimpleLiteral(end_literal, end_span)),
+            range_token.kind == TokenType::DoubleDotsEqual,
+            start_span.to(end_span),
+          ));
+        }
+        // error: the end of range pattern must be a literal
+        self
+          .errors
+          .push(CompileError::ExpectedExpressionAfterOperator {
+            op_str: range_token.raw,
+            pos: range_token.pos,
+          });
+        return None;
+      }
+    }
+    if !Parser::match_current_token_type(&self.current_token, TokenType::Vertical) {
+      return Some(MatchArmPattern::Single(first_arm));
+    }
+    let start = first_arm.span().start;
+    let mut alternatives = vec![first_arm];
+    while Parser::match_current_token_type(&self.current_token, TokenType::Vertical) {
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this '|'
+      alternatives.push(self.parse_match_single_arm()?);
+    }
+    Some(MatchArmPattern::Mutiple(
+      alternatives,
+      self.get_span_from(start),
+    ))
+  }
+
+  /// Examples: `match num { 1..3 => "small", 8 | 9 => { "big" } _ => "unknown", }`
+  pub fn parse_expression_match(&mut self) -> Option<ExpressionWithBlock> {
+    let match_token = self.consume_expected_token(TokenType::Match, "keyword 'match'")?;
+    let expression = self.parse_head_expression("an expression to match after 'match'")?;
+    self.consume_expected_token(
+      TokenType::LeftBrace,
+      "left brace '{' after the matched expression",
+    )?;
+    let mut arms: Vec<(MatchArmPattern, Box<Statement>)> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      let pattern = self.parse_match_arm_pattern()?;
+      if !Parser::match_current_token_type(&self.current_token, TokenType::FatArrow) {
+        // error: missing '=>' between pattern and body
+        Parser::collect_err_on_current_token_pos(&mut self.errors, &self.current_token, |pos| {
+          CompileError::ExpectedFatArrowAfterMatchPattern { pos }
+        });
+        return None;
+      }
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this '=>'
+      if Parser::match_current_token_type(&self.current_token, TokenType::LeftBrace) {
+        arms.push((pattern, self.parse_block_body()?));
+        // comma is optional after block body
+        if Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+          Parser::move_to_next_token(
+            &mut self.lexer,
+            &mut self.last_token,
+            &mut self.current_token,
+          ); // moves over this ','
+        }
+        continue;
+      }
+      let errors_count = self.errors.len();
+      if let Some(arm_body) = self.parse_expression() {
+        let arm_span = arm_body.span();
+        arms.push((
+          pattern,
+          Box::new(Statement::ExpressionStatement(arm_body, arm_span)),
+        ));
+      } else {
+        if self.errors.len() == errors_count {
+          // error: missing arm body
+          self.collect_expected_err_on_current_token("an expression or a block after '=>'");
+        }
+        return None;
+      }
+      if Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this ','
+      } else if !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+        // error: expression body must be followed by a comma, unless it's the last arm
+        Parser::collect_err_on_current_token_pos(&mut self.errors, &self.current_token, |pos| {
+          CompileError::ExpectedCommaAfterMatchArm { pos }
+        });
+        return None;
+      }
+    }
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this '}'
+    Some(ExpressionWithBlock::MatchExpression {
+      expression,
+      arms,
+      span: self.get_span_from(match_token.pos.start),
+    })
+  }
+
+  pub fn parse_expression_with_block(&mut self) -> Option<ExpressionWithBlock> {
+    match Parser::get_token_kind(&self.current_token) {
+      Some(TokenType::LeftBrace) => self.parse_bare_block(),
+      Some(TokenType::If) => self.parse_expression_if(),
+      Some(TokenType::Loop) => self.parse_expression_loop(),
+      Some(TokenType::While) => self.parse_expression_while(),
+      Some(TokenType::For) => self.parse_expression_for_each(),
+      Some(TokenType::Match) => self.parse_expression_match(),
+      _ => None,
+    }
+  }
+
+  fn match_expression_with_block_start(ref_current_token: &Option<Token>) -> bool {
+    Parser::match_current_token_types(
+      ref_current_token,
+      vec![
+        TokenType::LeftBrace,
+        TokenType::If,
+        TokenType::Loop,
+        TokenType::While,
+        TokenType::For,
+        TokenType::Match,
+      ],
+    )
+  }
+
+  pub fn parse_expression(&mut self) -> Option<Expression> {
+    if Parser::match_expression_with_block_start(&self.current_token) {
+      return self
+        .parse_expression_with_block()
+        .map(Expression::ExpressionWithBlock);
+    }
+    self
+      .parse_normal_expression()
+      .map(Expression::NormalExpression)
+  }
+
+  /// Comma separated parameters, ends before the given token type. <br>
+  /// Examples: `a, b, c`, `format, ...args`, empty list <br>
+  /// Returns: params, rest param
+  fn parse_param_list(
+    &mut self,
+    end_token_type: TokenType,
+  ) -> Option<(Vec<Identifier>, Option<Identifier>)> {
+    let mut params: Vec<Identifier> = vec![];
+    let mut rest_param: Option<Identifier> = None;
+    while !Parser::match_current_token_type(&self.current_token, end_token_type) {
+      let is_rest = Parser::match_current_token_type(&self.current_token, TokenType::ThreeDots);
+      if is_rest {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this '...'
+      }
+      let param_token = self.consume_expected_token(TokenType::Identifier, "a parameter name")?;
+      if rest_param.is_some() {
+        // error: rest param must be the last one, but it's fine to continue parsing
+        self.errors.push(CompileError::ParamAfterRestParam {
+          pos: param_token.pos,
+        });
+      } else if is_rest {
+        rest_param = Some(self.get_identifier(param_token));
+      } else {
+        params.push(self.get_identifier(param_token));
+      }
+      if !Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        break;
+      }
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ','
+    }
+    Some((params, rest_param))
+  }
+
+  /// Parameters list of a function, the whole parenthesized part is optional. <br>
+  /// Examples: `(a, b, c)`, `(format, ...args)`, `()` and `(self, type)` (only allowed for methods) <br>
+  /// Returns: has `self` receiver, params, rest param
+  fn parse_function_params(
+    &mut self,
+    is_method: bool,
+  ) -> Option<(bool, Vec<Identifier>, Option<Identifier>)> {
+    let mut has_self = false;
+    if !Parser::match_current_token_type(&self.current_token, TokenType::LeftParen) {
+      return Some((has_self, vec![], None));
+    }
+    let left_paren_token = Parser::get_current_token_meta_and_move_next(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+      "left parenthesis",
+    ); // moves over this '('
+    if is_method && Parser::match_current_token_type(&self.current_token, TokenType::_self_) {
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this 'self'
+      has_self = true;
+      if Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this ','
+      }
+    } else if !is_method
+      && Parser::match_current_token_type(&self.current_token, TokenType::RightParen)
+    {
+      // error: empty parentheses are forbidden, but it's fine to continue parsing
+      self
+        .errors
+        .push(CompileError::EmptyParenthesesInFunctionDeclaration {
+          pos: left_paren_token.pos,
+        });
+    }
+    let (params, rest_param) = self.parse_param_list(TokenType::RightParen)?;
+    self.consume_expected_token(
+      TokenType::RightParen,
+      "a comma ',' or right parenthesis ')' after parameter",
+    )?;
+    Some((has_self, params, rest_param))
+  }
+
+  /// A block of statements wrapped in braces. <br>
+  /// Examples: `{ println("Hello"); }`
+  /// A broken statement will be skipped, and parsing will go on with the next statement.
+  pub fn parse_block(&mut self) -> Option<Vec<Statement>> {
+    // the braces are counted once lexed, including this '{'
+    let block_depth = self.lexer.get_unclosed_pair_count(PairPunctuation::Brace);
+    self.consume_expected_token(TokenType::LeftBrace, "left brace '{' to start a block")?;
+    let mut statements: Vec<Statement> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      if Parser::match_current_token_types(
+        &self.current_token,
+        vec![
+          TokenType::EOF,
+          TokenType::Use,
+          TokenType::Pub,
+          TokenType::Trait,
+          TokenType::Impl,
+          TokenType::Enum,
+        ],
+      ) {
+        // error: the block is not closed before the next top statement,
+        // gives up this block and recovers at top level
+        self.collect_expected_err_on_current_token("right brace '}' to end the block");
+        return None;
+      }
+      let offset_before = self.lexer.offset_cursor;
+      match self.parse_statement() {
+        Some(statement) => statements.push(statement),
+        None => {
+          if self.lexer.offset_cursor == offset_before
+            && !Parser::match_current_token_types(
+              &self.current_token,
+              vec![TokenType::RightBrace, TokenType::EOF],
+            )
+          {
+            // nothing consumed by the broken statement, skips this token to make progress
+            Parser::move_to_next_token(
+              &mut self.lexer,
+              &mut self.last_token,
+              &mut self.current_token,
+            );
+          }
+          self.synchronize_in_block(block_depth);
+        }
+      }
+    }
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this '}'
+    Some(statements)
+  }
+
+  fn match_statement_start(ref_current_token: &Option<Token>) -> bool {
+    Parser::match_current_token_types(
+      ref_current_token,
+      vec![
+        TokenType::Var,
+        TokenType::Const,
+        TokenType::Return,
+        TokenType::Break,
+        TokenType::Continue,
+        TokenType::Fn,
+        TokenType::Async,
+        TokenType::If,
+        TokenType::Loop,
+        TokenType::While,
+        TokenType::For,
+        TokenType::Match,
+      ],
+    )
+  }
+
+  /// Panic-mode recovery in a block, skips tokens until: <br>
+  /// 1. a semicolon ';' which ends the broken statement (consumed) <br>
+  /// 2. a right brace '}' which ends the block <br>
+  /// 3. the start of next statement or top statement <br>
+  ///
+  /// Only the tokens directly in the block are considered, nested blocks are skipped as a whole.
+  fn synchronize_in_block(&mut self, block_depth: i32) {
+    loop {
+      let depth = self.lexer.get_unclosed_pair_count(PairPunctuation::Brace);
+      match Parser::get_token_kind(&self.current_token) {
+        None | Some(TokenType::EOF) => return,
+        // the count has decreased after lexing the right brace of this block
+        Some(TokenType::RightBrace) if depth < block_depth => return,
+        Some(TokenType::Semi) if depth == block_depth => {
+          Parser::move_to_next_token(
+            &mut self.lexer,
+            &mut self.last_token,
+            &mut self.current_token,
+          ); // moves over this ';'
+          return;
+        }
+        Some(TokenType::Use)
+        | Some(TokenType::Pub)
+        | Some(TokenType::Trait)
+        | Some(TokenType::Impl)
+        | Some(TokenType::Enum)
+          if depth == block_depth =>
+        {
+          return
+        }
+        _ if depth == block_depth && Parser::match_statement_start(&self.current_token) => return,
+        _ => Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ),
+      }
+    }
+  }
+
+  /// Body of a function, method or lambda, `return` is allowed but `break` and `continue`
+  /// of outer loops are not.
+  fn parse_function_body(&mut self) -> Option<Vec<Statement>> {
+    self.with_body_context(false, true, Parser::parse_block)
+  }
+
+  /// A statement in block. <br>
+  /// Examples: `var a = 1;`, `return a;`, `break;`, `continue;`, `fn inner { }`, `a + 1;`
+  pub fn parse_statement(&mut self) -> Option<Statement> {
+    let start = self.get_current_start();
+    match Parser::get_token_kind(&self.current_token) {
+      Some(TokenType::Var) | Some(TokenType::Const) => self.parse_variable_declaration(),
+      Some(TokenType::Return) => self.parse_return_statement(),
+      Some(TokenType::Break) => self.parse_break_statement(),
+      Some(TokenType::Continue) => self.parse_continue_statement(),
+      Some(TokenType::Fn) => self
+        .parse_function_declaration(start, false, false)
+        .map(Statement::FunctionDeclaration),
+      Some(TokenType::Async) => {
+        let is_async = self.consume_async_modifier();
+        if Parser::match_current_token_type(&self.current_token, TokenType::DollarColon) {
+          let lambda = self.parse_expression_lambda(start, is_async)?;
+          self.parse_expression_statement_end(Expression::NormalExpression(lambda))
+        } else {
+          self
+            .parse_function_declaration(start, false, is_async)
+            .map(Statement::FunctionDeclaration)
+        }
+      }
+      _ => {
+        let errors_count = self.errors.len();
+        match self.parse_expression() {
+          Some(expression) => self.parse_expression_statement_end(expression),
+          None => {
+            if self.errors.len() == errors_count {
+              // error: neither a statement nor the end of block
+              self
+                .consume_expected_token(TokenType::RightBrace, "right brace '}' to end the block");
+            }
+            None
+          }
+        }
+      }
+    }
+  }
+
+  /// Semicolon is required after a normal expression, and optional after an expression
+  /// with block. The last expression without semicolon is the value of the block.
+  fn parse_expression_statement_end(&mut self, expression: Expression) -> Option<Statement> {
+    let expression_span = expression.span();
+    if Parser::match_current_token_type(&self.current_token, TokenType::Semi) {
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ';'
+      return Some(Statement::ExpressionStatement(
+        expression,
+        self.get_span_from(expression_span.start),
+      ));
+    }
+    if Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      return Some(Statement::TrailingExpression(expression));
+    }
+    if let Expression::ExpressionWithBlock(_) = expression {
+      return Some(Statement::ExpressionStatement(expression, expression_span));
+    }
+    self.consume_expected_token(TokenType::Semi, "semicolon ';' after expression")?;
+    None
+  }
+
+  /// The value of the statement (if exists) is parsed until the semicolon. <br>
+  /// Returns: keyword token, the value
+  fn parse_jump_statement_value(
+    &mut self,
+    allow_value: bool,
+  ) -> Option<(ParsingTokenMeta, Option<Expression>)> {
+    let keyword_token = Parser::get_current_token_meta_and_move_next(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+      "keyword",
+    ); // moves over this 'return', 'break' or 'continue'
+    let mut value: Option<Expression> = None;
+    if allow_value && !Parser::match_current_token_type(&self.current_token, TokenType::Semi) {
+      let errors_count = self.errors.len();
+      value = self.parse_expression();
+      if value.is_none() {
+        if self.errors.len() == errors_count {
+          self.consume_expected_token(
+            TokenType::Semi,
+            &format!(
+              "an expression or semicolon ';' after '{}'",
+              keyword_token.raw
+            ),
+          );
+        }
+        return None;
+      }
+    }
+    self.consume_expected_token(
+      TokenType::Semi,
+      &format!("semicolon ';' after {} statement", keyword_token.raw),
+    )?;
+    Some((keyword_token, value))
+  }
+
+  /// Examples: `return;`, `return a + b;`
+  pub fn parse_return_statement(&mut self) -> Option<Statement> {
+    let (return_token, value) = self.parse_jump_statement_value(true)?;
+    if !self.is_in_function {
+      // error: nothing to return from, but it's fine to continue parsing
+      self.errors.push(CompileError::ReturnOutsideFunction {
+        pos: return_token.pos,
+      });
+    }
+    Some(Statement::ReturnStatement(
+      value,
+      self.get_span_from(return_token.pos.start),
+    ))
+  }
+
+  /// Examples: `break;`, `break result;`
+  pub fn parse_break_statement(&mut self) -> Option<Statement> {
+    let (break_token, value) = self.parse_jump_statement_value(true)?;
+    if !self.is_in_loop {
+      // error: nothing to break out, but it's fine to continue parsing
+      self.errors.push(CompileError::BreakOutsideLoop {
+        pos: break_token.pos,
+      });
+    }
+    Some(Statement::BreakStatement(
+      value,
+      self.get_span_from(break_token.pos.start),
+    ))
+  }
+
+  /// Examples: `continue;`
+  pub fn parse_continue_statement(&mut self) -> Option<Statement> {
+    let (continue_token, _) = self.parse_jump_statement_value(false)?;
+    if !self.is_in_loop {
+      // error: nothing to continue, but it's fine to continue parsing
+      self.errors.push(CompileError::ContinueOutsideLoop {
+        pos: continue_token.pos,
+      });
+    }
+    Some(Statement::ContinueStatement(
+      self.get_span_from(continue_token.pos.start),
+    ))
+  }
+
+  /// The rest element after `...` of an array destructing. <br>
+  /// Examples: `rest`, `[n4, ...rest]`
+  fn parse_array_destruct_rest(&mut self) -> Option<ArrayDestructRest> {
+    if Parser::match_current_token_type(&self.current_token, TokenType::LeftBracket) {
+      Some(ArrayDestructRest::ChildRest(Box::new(
+        self.parse_array_destruct()?,
+      )))
+    } else {
+      Some(ArrayDestructRest::Identifier(
+        self.consume_identifier("a rest variable name")?,
+      ))
+    }
+  }
+
+  /// Array destructuring, shared by declarations and assignments. <br>
+  /// Examples: `[a, b]`, `[a, b, ...rest]`, `[a, ...[b, ...rest]]`
+  pub fn parse_array_destruct(&mut self) -> Option<ArrayDestructAssign> {
+    let left_bracket_token = self.consume_expected_token(
+      TokenType::LeftBracket,
+      "left bracket '[' to start destructing",
+    )?;
+    let mut vars: Vec<Identifier> = vec![];
+    let mut rest: Option<ArrayDestructRest> = None;
+    loop {
+      if Parser::match_current_token_type(&self.current_token, TokenType::ThreeDots) {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this '...'
+        rest = Some(self.parse_array_destruct_rest()?);
+        break;
+      }
+      vars.push(self.consume_identifier("a variable name to destruct into")?);
+      if !Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        break;
+      }
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ','
+    }
+    let expected_desc = if rest.is_some() {
+      "right bracket ']' after the rest element"
+    } else {
+      "a comma ',' or right bracket ']' after variable"
+    };
+    self.consume_expected_token(TokenType::RightBracket, expected_desc)?;
+    Some(ArrayDestructAssign {
+      vars,
+      rest,
+      span: self.get_span_from(left_bracket_token.pos.start),
+    })
+  }
+
+  /// Examples: `var a = 1, b;`, `const [first, ...rest] = arr;`
+  pub fn parse_variable_declaration(&mut self) -> Option<Statement> {
+    let decl_token = Parser::get_current_token_meta_and_move_next(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+      "var or const",
+    ); // moves over this 'var' or 'const'
+    let is_const = decl_token.kind == TokenType::Const;
+    let mut decls: Vec<(DeclarationPattern, Option<Expression>)> = vec![];
+    loop {
+      let pattern = if Parser::match_current_token_type(&self.current_token, TokenType::LeftBracket)
+      {
+        DeclarationPattern::Destruct(self.parse_array_destruct()?)
+      } else {
+        DeclarationPattern::Identifier(self.consume_identifier("a variable name")?)
+      };
+      let mut initializer: Option<Expression> = None;
+      if Parser::match_current_token_type(&self.current_token, TokenType::Equal) {
+        let equal_token = Parser::get_current_token_meta_and_move_next(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+          "equal sign",
+        ); // moves over this '='
+        let errors_count = self.errors.len();
+        initializer = self.parse_expression();
+        if initializer.is_none() {
+          if self.errors.len() == errors_count {
+            // error: expected an initializer expression after '='
+            self
+              .errors
+              .push(CompileError::ExpectedExpressionAfterOperator {
+                op_str: equal_token.raw,
+                pos: equal_token.pos,
+              });
+          }
+          return None;
+        }
+      } else if let Some(current_pos) = Parser::get_token_pos(&self.current_token) {
+        // error: constants and destructing must be initialized, but it's fine to continue parsing
+        if is_const {
+          self
+            .errors
+            .push(CompileError::ConstDeclarationWithoutInitializer { pos: current_pos });
+        } else if let DeclarationPattern::Destruct(_) = pattern {
+          self
+            .errors
+            .push(CompileError::DestructDeclarationWithoutInitializer { pos: current_pos });
+        }
+      }
+      decls.push((pattern, initializer));
+      if !Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        break;
+      }
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ','
+    }
+    self.consume_expected_token(TokenType::Semi, "semicolon ';' after variable declaration")?;
+    Some(Statement::VariableDeclaration {
+      is_const,
+      decls,
+      span: self.get_span_from(decl_token.pos.start),
+    })
+  }
+
+  /// Examples: `std::io`, `crate::enums`
+  fn parse_use_path(&mut self) -> Option<Vec<Identifier>> {
+    let mut path: Vec<Identifier> = vec![];
+    if Parser::match_current_token_type(&self.current_token, TokenType::Crate) {
+      let crate_token = Parser::get_current_token_meta_and_move_next(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+        "crate symbol",
+      ); // moves over this 'crate'
+      path.push(self.get_identifier(crate_token));
+      // 'crate' can't be used alone
+      self.consume_expected_token(TokenType::DoubleColon, "double colon '::' after 'crate'")?;
+    }
+    path.push(self.consume_identifier("a module name")?);
+    while Parser::match_current_token_type(&self.current_token, TokenType::DoubleColon) {
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this '::'
+      path.push(self.consume_identifier("an identifier after double colon '::'")?);
+    }
+    Some(path)
+  }
+
+  /// A group of use endpoints, the path before the group has been consumed,
+  /// the use tree starts at `start`. <br>
+  /// Examples: `{ snake_to_camel, camel_to_snake, }`
+  fn parse_use_group(&mut self, start: usize, path: Vec<Identifier>) -> Option<UseTree> {
+    let left_brace_token =
+      self.consume_expected_token(TokenType::LeftBrace, "left brace '{' to start use tree")?;
+    let mut children: Vec<UseTree> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      children.push(self.parse_use_endpoint()?);
+      if !Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        break;
+      }
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ','
+    }
+    self.consume_expected_token(
+      TokenType::RightBrace,
+      "a comma ',' or right brace '}' after use endpoint",
+    )?;
+    if children.is_empty() {
+      // error: nothing is used, but it's fine to continue parsing
+      self.errors.push(CompileError::EmptyUseTree {
+        pos: left_brace_token.pos,
+      });
+    }
+    if Parser::match_current_token_type(&self.current_token, TokenType::As) {
+      // error: a group can't be aliased, skip the alias and continue parsing
+      let as_token = Parser::get_current_token_meta_and_move_next(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+        "keyword 'as'",
+      ); // moves over this 'as'
+      self
+        .errors
+        .push(CompileError::AliasOnUseGroup { pos: as_token.pos });
+      self.consume_identifier("an alias name after 'as'")?;
+    }
+    Some(UseTree::Group {
+      path,
+      children,
+      span: self.get_span_from(start),
+    })
+  }
+
+  /// Examples: `std::io as stdio`, `crate::fibonacci.fib`, `std::strings { snake_to_camel }`
+  fn parse_use_endpoint(&mut self) -> Option<UseTree> {
+    let start = self.get_current_start();
+    let path = self.parse_use_path()?;
+    match Parser::get_token_kind(&self.current_token) {
+      Some(TokenType::Dot) => {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this '.'
+        let item = self.parse_use_endpoint()?;
+        Some(UseTree::Select {
+          path,
+          item: Box::new(item),
+          span: self.get_span_from(start),
+        })
+      }
+      Some(TokenType::LeftBrace) => self.parse_use_group(start, path),
+      Some(TokenType::As) => {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this 'as'
+        let alias = self.consume_identifier("an alias name after 'as'")?;
+        Some(UseTree::Path {
+          path,
+          alias: Some(alias),
+          span: self.get_span_from(start),
+        })
+      }
+      _ => Some(UseTree::Path {
+        path,
+        alias: None,
+        span: self.get_span_from(start),
+      }),
+    }
+  }
+
+  /// Examples: `use std::io as stdio;`, `use crate::enums.ExceptionResponse;`,
+  /// `use { std::fs, std::strings { snake_to_camel } }`
+  pub fn parse_use_statement(&mut self) -> Option<TopStatement> {
+    let use_token = self.consume_expected_token(TokenType::Use, "keyword 'use'")?;
+    if Parser::match_current_token_type(&self.current_token, TokenType::LeftBrace) {
+      // a top use tree needs no semicolon
+      let use_tree = self.parse_use_group(self.get_current_start(), vec![])?;
+      return Some(TopStatement::UseStatement(
+        use_tree,
+        None,
+        self.get_span_from(use_token.pos.start),
+      ));
+    }
+    let use_tree = self.parse_use_endpoint()?;
+    self.consume_expected_token(TokenType::Semi, "semicolon ';' after use statement")?;
+    Some(TopStatement::UseStatement(
+      use_tree,
+      None,
+      self.get_span_from(use_token.pos.start),
+    ))
+  }
+
+  /// Payload fields of an enum variant, the variant name has been consumed. <br>
+  /// Examples: `{ timeCost, issueMsg }`
+  fn parse_enum_variant_fields(&mut self) -> Option<Vec<Identifier>> {
+    let mut fields: Vec<Identifier> = vec![];
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this '{'
+    loop {
+      fields.push(self.consume_identifier("a payload field name")?);
+      if !Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        break;
+      }
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ','
+      if Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+        break;
+      }
+    }
+    self.consume_expected_token(
+      TokenType::RightBrace,
+      "a comma ',' or right brace '}' after payload field",
+    )?;
+    Some(fields)
+  }
+
+  /// The 'pub' modifier has been consumed by caller if exists, the enum starts at `start`. <br>
+  /// Examples: `enum HttpStatus { NotFound, Forbidden }`,
+  /// `pub enum ExceptionResponse { ResourceNotFound, NetworkIssue { timeCost, issueMsg }, }`
+  pub fn parse_enum_statement(&mut self, start: usize, is_pub: bool) -> Option<TopStatement> {
+    self.consume_expected_token(TokenType::Enum, "keyword 'enum'")?;
+    let name = self.consume_identifier("an enum name")?;
+    self.consume_expected_token(TokenType::LeftBrace, "left brace '{' after enum name")?;
+    let mut variants: Vec<(Identifier, Vec<Identifier>, Option<String>)> = vec![];
+    // positions of the declared variants, for reporting duplicates
+    let mut variant_positions: Vec<Position> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      let variant_doc = self.get_doc_comment();
+      let variant_token =
+        self.consume_expected_token(TokenType::Identifier, "an enum variant name")?;
+      let mut fields: Vec<Identifier> = vec![];
+      if Parser::match_current_token_type(&self.current_token, TokenType::LeftBrace) {
+        fields = self.parse_enum_variant_fields()?;
+      }
+      if let Some(existed_index) = variants
+        .iter()
+        .position(|(existed_name, ..)| existed_name.name == variant_token.raw)
+      {
+        // error: the variant has been declared, but it's fine to continue parsing
+        self.errors.push(CompileError::DuplicateEnumVariant {
+          name: variant_token.raw,
+          pos: variant_token.pos,
+          first_pos: variant_positions[existed_index],
+        });
+      } else {
+        variant_positions.push(variant_token.pos);
+        variants.push((self.get_identifier(variant_token), fields, variant_doc));
+      }
+      if !Parser::match_current_token_type(&self.current_token, TokenType::Comma) {
+        break;
+      }
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this ','
+    }
+    self.consume_expected_token(
+      TokenType::RightBrace,
+      "a comma ',' or right brace '}' after enum variant",
+    )?;
+    Some(TopStatement::EnumStatement {
+      is_pub,
+      name,
+      variants,
+      doc: None,
+      span: self.get_span_from(start),
+    })
+  }
+
+  /// The 'pub' and 'async' modifiers have been consumed by caller if exist,
+  /// the function starts at `start`. <br>
+  /// Examples: `fn add(a, b) { a + b; }`, `pub async fn fetch(url, ...options) { }`
+  pub fn parse_function_declaration(
+    &mut self,
+    start: usize,
+    is_pub: bool,
+    is_async: bool,
+  ) -> Option<FunctionDeclaration> {
+    self.consume_expected_token(TokenType::Fn, "keyword 'fn'")?;
+    let name = self.consume_identifier("a function name")?;
+    let (_, params, rest_param) = self.parse_function_params(false)?;
+    let body = self.parse_function_body()?;
+    Some(FunctionDeclaration::new(
+      is_pub,
+      is_async,
+      name,
+      params,
+      rest_param,
+      body,
+      self.get_span_from(start),
+    ))
+  }
+
+  /// Examples: `struct People { pub name; age; desc?; }`
+  pub fn parse_struct_declaration(&mut self) -> Option<TopStatement> {
+    let struct_token = self.consume_expected_token(TokenType::Struct, "keyword 'struct'")?;
+    let name = self.consume_identifier("a struct name")?;
+    self.consume_expected_token(TokenType::LeftBrace, "left brace '{' after struct name")?;
+    let mut fields: Vec<StructField> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      let field_doc = self.get_doc_comment();
+      let field_start = self.get_current_start();
+      let mut is_pub = false;
+      if Parser::match_current_token_type(&self.current_token, TokenType::Pub) {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this 'pub'
+        is_pub = true;
+      }
+      let field_name = self.consume_identifier("a field name")?;
+      let mut is_optional = false;
+      if Parser::match_current_token_type(&self.current_token, TokenType::Question) {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this '?'
+        is_optional = true;
+      }
+      self.consume_expected_token(TokenType::Semi, "semicolon ';' after struct field")?;
+      fields.push(
+        StructField::new(
+          field_name,
+          is_pub,
+          is_optional,
+          self.get_span_from(field_start),
+        )
+        .with_doc(field_doc),
+      );
+    }
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this '}'
+    Some(TopStatement::StructDeclaration {
+      name,
+      fields,
+      doc: None,
+      span: self.get_span_from(struct_token.pos.start),
+    })
+  }
+
+  /// Head of a method in trait or impl body, ends before `;` or the method body. <br>
+  /// Examples: `stand()`, `async walk(distance)`, `gooseStep(self, type)`
+  fn parse_method_head(&mut self) -> Option<MethodHead> {
+    let is_async = self.consume_async_modifier();
+    let method_name = self.consume_identifier("a method name")?;
+    let (has_self, params, rest_param) = self.parse_function_params(true)?;
+    Some((is_async, method_name, has_self, params, rest_param))
+  }
+
+  /// Examples: `trait Walkable { stand(); async walk(distance); startRacing(self); }`
+  pub fn parse_trait_declaration(&mut self) -> Option<TopStatement> {
+    let trait_token = self.consume_expected_token(TokenType::Trait, "keyword 'trait'")?;
+    let name = self.consume_identifier("a trait name")?;
+    self.consume_expected_token(TokenType::LeftBrace, "left brace '{' after trait name")?;
+    let mut methods: Vec<(FunctionSignature, bool)> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      let method_doc = self.get_doc_comment();
+      let method_start = self.get_current_start();
+      let (is_async, method_name, has_self, params, rest_param) = self.parse_method_head()?;
+      self.consume_expected_token(TokenType::Semi, "semicolon ';' after method signature")?;
+      methods.push((
+        FunctionSignature::new(
+          false,
+          is_async,
+          method_name,
+          params,
+          rest_param,
+          self.get_span_from(method_start),
+        )
+        .with_doc(method_doc),
+        has_self,
+      ));
+    }
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this '}'
+    Some(TopStatement::TraitDeclaration {
+      name,
+      methods,
+      doc: None,
+      span: self.get_span_from(trait_token.pos.start),
+    })
+  }
+
+  /// Examples: `impl People { introduce(self) { } }`,
+  /// `impl Walkable for People { stand() { println("standing"); } }`
+  pub fn parse_impl_declaration(&mut self) -> Option<TopStatement> {
+    let impl_token = self.consume_expected_token(TokenType::Impl, "keyword 'impl'")?;
+    let mut trait_name: Option<Identifier> = None;
+    let mut struct_name = self.consume_identifier("a trait or struct name")?;
+    if Parser::match_current_token_type(&self.current_token, TokenType::For) {
+      Parser::move_to_next_token(
+        &mut self.lexer,
+        &mut self.last_token,
+        &mut self.current_token,
+      ); // moves over this 'for'
+      trait_name = Some(struct_name);
+      struct_name = self.consume_identifier("a struct name after 'for'")?;
+    }
+    self.consume_expected_token(TokenType::LeftBrace, "left brace '{' after struct name")?;
+    let mut methods: Vec<(FunctionDeclaration, bool)> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::RightBrace) {
+      let method_doc = self.get_doc_comment();
+      let method_start = self.get_current_start();
+      let (is_async, method_name, has_self, params, rest_param) = self.parse_method_head()?;
+      let body = self.parse_function_body()?;
+      methods.push((
+        FunctionDeclaration::new(
+          false,
+          is_async,
+          method_name,
+          params,
+          rest_param,
+          body,
+          self.get_span_from(method_start),
+        )
+        .with_doc(method_doc),
+        has_self,
+      ));
+    }
+    Parser::move_to_next_token(
+      &mut self.lexer,
+      &mut self.last_token,
+      &mut self.current_token,
+    ); // moves over this '}'
+    Some(TopStatement::ImplDeclaration {
+      trait_name,
+      struct_name,
+      methods,
+      doc: None,
+      span: self.get_span_from(impl_token.pos.start),
+    })
+  }
+
+  /// The doc comments before the top statement are attached to it.
+  pub fn parse_top_statement(&mut self) -> Option<TopStatement> {
+    let doc = self.get_doc_comment();
+    let start = self.get_current_start();
+    let top_statement = match Parser::get_token_kind(&self.current_token) {
+      Some(TokenType::Use) => self.parse_use_statement(),
+      Some(TokenType::Enum) => self.parse_enum_statement(start, false),
+      Some(TokenType::Async) | Some(TokenType::Fn) => {
+        let is_async = self.consume_async_modifier();
+        self
+          .parse_function_declaration(start, false, is_async)
+          .map(TopStatement::FunctionDeclaration)
+      }
+      Some(TokenType::Pub) => {
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ); // moves over this 'pub'
+        if Parser::match_current_token_type(&self.current_token, TokenType::Enum) {
+          self.parse_enum_statement(start, true)
+        } else if Parser::match_current_token_types(
+          &self.current_token,
+          vec![TokenType::Async, TokenType::Fn],
+        ) {
+          let is_async = self.consume_async_modifier();
+          self
+            .parse_function_declaration(start, true, is_async)
+            .map(TopStatement::FunctionDeclaration)
+        } else {
+          self.consume_expected_token(TokenType::Fn, "'enum', 'async' or 'fn' after 'pub'");
+          None
+        }
+      }
+      Some(TokenType::Struct) => self.parse_struct_declaration(),
+      Some(TokenType::Trait) => self.parse_trait_declaration(),
+      Some(TokenType::Impl) => self.parse_impl_declaration(),
+      _ => {
+        // error: the token can't be the start of a top statement
+        if let Some(current_pos) = Parser::get_token_pos(&self.current_token) {
+          self.errors.push(CompileError::UnexpectedToken {
+            token_name: Parser::get_token_display_name(&self.current_token),
+            pos: current_pos,
+          });
+        }
+        None
+      }
+    };
+    top_statement.map(|top_statement| top_statement.with_doc(doc))
+  }
+
+  /// Panic-mode recovery at top level, skips tokens until the start of next top statement.
+  /// `fn` and `async` may also start a nested function, so they are only considered
+  /// when they are not in any block.
+  fn synchronize_at_top(&mut self) {
+    loop {
+      match Parser::get_token_kind(&self.current_token) {
+        None
+        | Some(TokenType::EOF)
+        | Some(TokenType::Use)
+        | Some(TokenType::Pub)
+        | Some(TokenType::Struct)
+        | Some(TokenType::Trait)
+        | Some(TokenType::Impl)
+        | Some(TokenType::Enum) => return,
+        Some(TokenType::Fn) | Some(TokenType::Async)
+          if self.lexer.get_unclosed_pair_count(PairPunctuation::Brace) == 0 =>
+        {
+          return
+        }
+        _ => Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        ),
+      }
+    }
+  }
+
+  /// Entry of parsing a whole file: `top_statement* EOF` <br>
+  /// Returns: top statements, errors collected during lexing and parsing
+  pub fn parse_program(&mut self) -> (Vec<TopStatement>, Vec<CompileError>) {
+    let mut top_statements: Vec<TopStatement> = vec![];
+    while !Parser::match_current_token_type(&self.current_token, TokenType::EOF) {
+      let offset_before = self.lexer.offset_cursor;
+      if let Some(top_statement) = self.parse_top_statement() {
+        top_statements.push(top_statement);
+        continue;
+      }
+      if self.lexer.offset_cursor == offset_before {
+        // nothing consumed by the broken top statement, skips this token to make progress
+        Parser::move_to_next_token(
+          &mut self.lexer,
+          &mut self.last_token,
+          &mut self.current_token,
+        );
+      }
+      self.synchronize_at_top();
+    }
+    self.lexer.collect_unclosed_pair_errors();
+    let mut errors = std::mem::take(&mut self.lexer.errors);
+    errors.append(&mut self.errors);
+    // the lexer errors are merged into the parser errors in source order,
+    // the stable sort keeps the order of errors at the same position
+    errors.sort_by_key(|error| error.get_pos().start);
+    (top_statements, errors)
+  }
 }
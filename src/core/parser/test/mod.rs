@@ -1 +1,159 @@
 mod test_pare_expr_atoms;
+mod test_parse_assignment;
+mod test_parse_doc_comments;
+mod test_parse_enum;
+mod test_parse_function;
+mod test_parse_if;
+mod test_parse_lambda;
+mod test_parse_loop;
+mod test_parse_match;
+mod test_parse_operators;
+mod test_parse_postfix;
+mod test_parse_program;
+mod test_parse_recovery;
+mod test_parse_spans;
+mod test_parse_statement;
+mod test_parse_struct;
+mod test_parse_trait_impl;
+mod test_parse_use;
+mod test_parse_var_decl;
+
+#[cfg(test)]
+use crate::core::shared::ast::{
+  expressions::{
+    ArrayDestructAssign, ArrayDestructRest, AssignmentLeftHand, Expression, NamePathHead,
+    NormalExpression, SimpleLiteral, UnaryOperator,
+  },
+  statements::Statement,
+};
+#[cfg(test)]
+use crate::core::shared::span::Span;
+
+/// The source text covered by the span.
+#[cfg(test)]
+pub fn get_span_text(source: &str, span: Span) -> &str {
+  &source[span.start..span.end]
+}
+
+/// Prints a normal expression in a fully parenthesized form, for comparing precedence easily.
+#[cfg(test)]
+fn stringify_normal_expression(expr: &NormalExpression) -> String {
+  match expr {
+    NormalExpression::SimpleLiteral(SimpleLiteral::DecimalLiteral(raw), _) => raw.clone(),
+    NormalExpression::NamePathExpression(name_path) => match &name_path.head {
+      NamePathHead::Identifier(identifier) => identifier.name.clone(),
+      _ => String::from("<path>"),
+    },
+    NormalExpression::Grouping(inner, _) => stringify_expression(inner),
+    NormalExpression::UnaryExpression(operand, UnaryOperator::Negation, _) => {
+      format!("(-{})", stringify_normal_expression(operand))
+    }
+    NormalExpression::UnaryExpression(operand, UnaryOperator::Not, _) => {
+      format!("(!{})", stringify_normal_expression(operand))
+    }
+    NormalExpression::BinaryExpression(left, operator, right, _) => format!(
+      "({} {:?} {})",
+      stringify_normal_expression(left),
+      operator,
+      stringify_normal_expression(right)
+    ),
+    NormalExpression::SimpleLiteral(SimpleLiteral::StringLiteral(raw), _) => format!("{:?}", raw),
+    NormalExpression::GetExpression(source, field, optional, _) => format!(
+      "{}{}{}",
+      stringify_normal_expression(source),
+      if *optional { "?." } else { "." },
+      field.name
+    ),
+    NormalExpression::CallExpression(source, arguments, _) => format!(
+      "{}({})",
+      stringify_normal_expression(source),
+      arguments
+        .iter()
+        .map(stringify_expression)
+        .collect::<Vec<String>>()
+        .join(", ")
+    ),
+    NormalExpression::IndexExpression(source, index, _) => format!(
+      "{}[{}]",
+      stringify_normal_expression(source),
+      stringify_expression(index)
+    ),
+    NormalExpression::RangeExpression(start, end, inclusive, _) => format!(
+      "({}{}{})",
+      stringify_normal_expression(start),
+      if *inclusive { "..=" } else { ".." },
+      stringify_normal_expression(end)
+    ),
+    NormalExpression::LambdaExpression(lambda) => {
+      let mut params: Vec<String> = lambda
+        .params
+        .iter()
+        .map(|param| param.name.clone())
+        .collect();
+      if let Some(rest_param) = &lambda.rest_param {
+        params.push(format!("...{}", rest_param.name));
+      }
+      let body = match lambda.body.as_slice() {
+        [Statement::TrailingExpression(body_expr)] => stringify_expression(body_expr),
+        _ => String::from("<block>"),
+      };
+      format!(
+        "({}$: {} -> {})",
+        if lambda.is_async { "async " } else { "" },
+        params.join(", "),
+        body
+      )
+    }
+    NormalExpression::ArrayLiteral(elements, rest, _) => {
+      let mut elements: Vec<String> = elements.iter().map(stringify_expression).collect();
+      if let Some(rest) = rest {
+        elements.push(format!("...{}", stringify_normal_expression(rest)));
+      }
+      format!("[{}]", elements.join(", "))
+    }
+    NormalExpression::AssignmentExpression(left_hand, value, _) => format!(
+      "({} = {})",
+      stringify_assignment_left_hand(left_hand),
+      stringify_expression(value)
+    ),
+    NormalExpression::CompoundAssignmentExpression(left_hand, operator, value, _) => format!(
+      "({} {:?}= {})",
+      stringify_assignment_left_hand(left_hand),
+      operator,
+      stringify_expression(value)
+    ),
+    _ => String::from("<unknown>"),
+  }
+}
+
+#[cfg(test)]
+pub fn stringify_expression(expr: &Expression) -> String {
+  match expr {
+    Expression::NormalExpression(normal_expr) => stringify_normal_expression(normal_expr),
+    Expression::ExpressionWithBlock(_) => String::from("<block>"),
+  }
+}
+
+#[cfg(test)]
+fn stringify_array_destruct(destruct: &ArrayDestructAssign) -> String {
+  let mut elements: Vec<String> = destruct.vars.iter().map(|var| var.name.clone()).collect();
+  match &destruct.rest {
+    Some(ArrayDestructRest::Identifier(rest)) => elements.push(format!("...{}", rest.name)),
+    Some(ArrayDestructRest::ChildRest(child)) => {
+      elements.push(format!("...{}", stringify_array_destruct(child)))
+    }
+    None => {}
+  }
+  format!("[{}]", elements.join(", "))
+}
+
+#[cfg(test)]
+fn stringify_assignment_left_hand(left_hand: &AssignmentLeftHand) -> String {
+  match left_hand {
+    AssignmentLeftHand::Identifier(identifier) => identifier.name.clone(),
+    AssignmentLeftHand::Destruct(destruct) => stringify_array_destruct(destruct),
+    AssignmentLeftHand::GetExpression(expr) | AssignmentLeftHand::IndexExpression(expr) => {
+      stringify_expression(expr)
+    }
+  }
+}
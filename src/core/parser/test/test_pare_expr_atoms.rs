@@ -40,25 +40,24 @@ fn test_parse_expression_simple_literal() {
 fn test_parse_expression_grouping() {
   use crate::core::{
     parser::impls::Parser,
-    shared::ast::{
-      expressions::{
+    shared::{
+      ast::expressions::{
         Expression::NormalExpression,
         NormalExpression::{Grouping, SimpleLiteral},
         SimpleLiteral::FloatLiteral,
       },
-      Position,
+      span::{FileId, Span},
     },
   };
   let mut parser = Parser::new(r#"(3.1415)"#);
   let expr_test = parser.parse_expression_grouping();
 
   assert_eq!(expr_test.is_some(), true);
-  if let Some(NormalExpression(Grouping(expr, left_paren_pos, right_paren_pos))) = expr_test {
-    if let NormalExpression(SimpleLiteral(FloatLiteral(lit_raw), lit_pos)) = expr.as_ref() {
-      assert_eq!(left_paren_pos, Position { line: 1, col: 2 });
-      assert_eq!(right_paren_pos, Position { line: 1, col: 9 });
+  if let Some(NormalExpression(Grouping(expr, grouping_span))) = expr_test {
+    if let NormalExpression(SimpleLiteral(FloatLiteral(lit_raw), lit_span)) = expr.as_ref() {
+      assert_eq!(grouping_span, Span::new(FileId(0), 0, 8));
       assert_eq!(*lit_raw, "3.1415".to_string());
-      assert_eq!(*lit_pos, Position { line: 1, col: 8 });
+      assert_eq!(*lit_span, Span::new(FileId(0), 1, 7));
     } else {
       panic!("Can not correctly parse 3.1415 as a float literal.");
     }
@@ -71,35 +70,26 @@ fn test_parse_expression_grouping() {
 fn test_parse_expression_array_literal() {
   use crate::core::{
     parser::impls::Parser,
-    shared::ast::{
-      expressions::{
+    shared::{
+      ast::expressions::{
         Expression::NormalExpression,
         NormalExpression::{ArrayLiteral, SimpleLiteral},
         SimpleLiteral::DecimalLiteral,
       },
-      Position,
+      span::{FileId, Span},
     },
   };
   let mut parser = Parser::new(r#"[1, 2, 3]"#);
   let expr_test = parser.parse_expression_array_literal();
 
   assert_eq!(expr_test.is_some(), true);
-  if let Some(NormalExpression(ArrayLiteral(expr_list, left_bracket_pos, right_bracket_pos))) =
-    expr_test
-  {
-    assert_eq!(left_bracket_pos, Position { line: 1, col: 2 });
-    assert_eq!(right_bracket_pos, Position { line: 1, col: 10 });
+  if let Some(NormalExpression(ArrayLiteral(expr_list, None, array_span))) = expr_test {
+    assert_eq!(array_span, Span::new(FileId(0), 0, 9));
     assert_eq!(expr_list.len(), 3);
     for i in 0..expr_list.len() {
-      if let NormalExpression(SimpleLiteral(DecimalLiteral(lit_raw), lit_pos)) = &expr_list[i] {
+      if let NormalExpression(SimpleLiteral(DecimalLiteral(lit_raw), lit_span)) = &expr_list[i] {
         assert_eq!(*lit_raw, (i + 1).to_string());
-        assert_eq!(
-          *lit_pos,
-          Position {
-            line: 1,
-            col: 3 * (i + 1)
-          }
-        );
+        assert_eq!(*lit_span, Span::new(FileId(0), 3 * i + 1, 3 * i + 2));
       } else {
         panic!("Can not correctly parse {} as a decimal literal.", i + 1);
       }
@@ -113,13 +103,16 @@ fn test_parse_expression_array_literal() {
 fn test_parse_expression_name_path_expression() {
   use crate::core::{
     parser::impls::Parser,
-    shared::ast::{
-      expressions::{
-        Expression::NormalExpression,
-        NormalExpression::NamePathExpression,
-        NamePathHead,
+    shared::{
+      ast::{
+        expressions::{
+          Expression::NormalExpression,
+          NormalExpression::NamePathExpression,
+          NamePathHead,
+        },
+        Identifier as IdentifierStruct,
       },
-      Identifier as IdentifierStruct, Position,
+      span::{FileId, Span},
     },
   };
   let mut parser = Parser::new(r#"self::foo::bar"#);
@@ -127,18 +120,19 @@ fn test_parse_expression_name_path_expression() {
   let suffix_answer = vec![
     IdentifierStruct {
       name: String::from("foo"),
-      pos: Position { line: 1, col: 10 },
+      span: Span::new(FileId(0), 6, 9),
     },
     IdentifierStruct {
       name: String::from("bar"),
-      pos: Position { line: 1, col: 15 },
+      span: Span::new(FileId(0), 11, 14),
     },
   ];
 
   assert_eq!(expr_test.is_some(), true);
   if let Some(NormalExpression(NamePathExpression(name_path_expr))) = expr_test {
-    if let NamePathHead::SelfSymbol(head_pos) = name_path_expr.head {
-      assert_eq!(head_pos, Position { line: 1, col: 5 });
+    assert_eq!(name_path_expr.span, Span::new(FileId(0), 0, 14));
+    if let NamePathHead::SelfSymbol(head_span) = name_path_expr.head {
+      assert_eq!(head_span, Span::new(FileId(0), 0, 4));
 
       if let Some(suffix_fragments) = name_path_expr.suffix {
         suffix_fragments
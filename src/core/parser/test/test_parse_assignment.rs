@@ -0,0 +1,99 @@
+#[test]
+fn test_parse_assignment_expressions() {
+  use super::stringify_expression;
+  use crate::core::parser::impls::Parser;
+
+  let cases = [
+    ("price = 200", "(price = 200)"),
+    ("a = b = 1", "(a = (b = 1))"),
+    ("a = 1..3", "(a = (1..3))"),
+    ("self.name = name", "(<path>.name = name)"),
+    ("arr[0] = arr[1] + 1", "(arr[0] = (arr[1] Addition 1))"),
+    ("arr[i] += 1", "(arr[i] Addition= 1)"),
+    ("a **= b ||= c", "(a Exponent= (b LogicalOr= c))"),
+    ("flag &&= a || b", "(flag LogicalAnd= (a LogicalOr b))"),
+    ("x <<= 2", "(x BitwiseShiftLeft= 2)"),
+    ("[a, b] = [b, a]", "([a, b] = [b, a])"),
+    ("[first, ...rest] = arr", "([first, ...rest] = arr)"),
+    (
+      "[n1, n2, n3, ...[n4, ...rest]] = bigger_arr",
+      "([n1, n2, n3, ...[n4, ...rest]] = bigger_arr)",
+    ),
+    ("a = if b { 1 } else { 2 }", "(a = <block>)"),
+  ];
+  for (source, expected) in cases {
+    let mut parser = Parser::new(source);
+    let expr = parser.parse_expression();
+    assert!(parser.errors.is_empty(), "{}: {:?}", source, parser.errors);
+    assert_eq!(
+      stringify_expression(&expr.expect("Can not parse the assignment.")),
+      expected
+    );
+  }
+}
+
+#[test]
+fn test_parse_assignment_in_program() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let mut parser = Parser::new(include_str!("../../../../examples/src/assignment.n"));
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let Some(TopStatement::FunctionDeclaration(function)) = top_statements.first() else {
+    panic!("Can not correctly parse a function declaration.");
+  };
+  assert_eq!(function.body().len(), 7);
+}
+
+#[test]
+fn test_parse_assignment_invalid_targets() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+
+  for (source, col) in [
+    ("a?.b = 1", 7),
+    ("a?.b.c = 1", 9),
+    ("a?.b[0] = 1", 10),
+    ("f() = 2", 6),
+    ("1 + a = 3", 8),
+    ("A::B = 4", 7),
+    ("[a, 1] = arr", 9),
+    ("[a, b] += arr", 10),
+    ("[a.b, ...rest] = arr", 17),
+    ("[a, ...b.c] = arr", 14),
+    ("1 + [a, ...b] = c", 16),
+  ] {
+    let mut parser = Parser::new(source);
+    let expr = parser.parse_expression();
+    assert!(expr.is_none());
+    assert_eq!(parser.errors.len(), 1, "{}: {:?}", source, parser.errors);
+    if let CompileError::InvalidAssignmentTarget { pos } = &parser.errors[0] {
+      assert_eq!(pos.col, col, "{}", source);
+    } else {
+      panic!(
+        "Expected an error about invalid assignment target: {}",
+        source
+      );
+    }
+  }
+
+  let mut parser = Parser::new("a = ;");
+  parser.parse_expression();
+  assert!(matches!(
+    parser.errors.first(),
+    Some(CompileError::ExpectedExpressionAfterOperator { op_str, .. }) if op_str == "="
+  ));
+
+  for source in [
+    "[a, ...rest];",
+    "f([a, ...[b, ...rest]]);",
+    "[...rest].a = 1;",
+  ] {
+    let mut parser = Parser::new(source);
+    parser.parse_expression();
+    assert_eq!(parser.errors.len(), 1, "{}: {:?}", source, parser.errors);
+    assert!(matches!(
+      &parser.errors[0],
+      CompileError::ExpectedToken { expected, .. }
+        if expected == "equal sign '=' after array destructing"
+    ));
+  }
+}
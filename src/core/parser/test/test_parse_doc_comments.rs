@@ -0,0 +1,118 @@
+#[test]
+fn test_parse_doc_comments_of_top_statements() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let source = "/// The io module.
+use std::io;
+
+/// Adds two numbers.
+/// Returns the sum.
+pub async fn add(a, b) { a + b }
+
+// a normal comment is not a doc
+fn main { }
+
+/**
+ * The HTTP status.
+ */
+enum Status {
+  /// Found nothing.
+  NotFound,
+  Forbidden { reason },
+  /** Moved to another place. */
+  Moved { location },
+}
+
+/// A person.
+struct People {
+  /// The full name.
+  pub name;
+  age;
+}
+
+/// Things that walk.
+trait Walkable {
+  /// Stands still.
+  stand();
+  async walk(distance);
+}
+
+/// The walking of people.
+impl Walkable for People {
+  stand() { }
+  /// Walks slowly.
+  async walk(distance) { }
+}
+/// dangling at the end
+";
+  let mut parser = Parser::new(source);
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let docs: Vec<Option<&str>> = top_statements
+    .iter()
+    .map(|top_statement| top_statement.doc())
+    .collect();
+  assert_eq!(
+    docs,
+    vec![
+      Some("The io module."),
+      Some("Adds two numbers.\nReturns the sum."),
+      None,
+      Some("The HTTP status."),
+      Some("A person."),
+      Some("Things that walk."),
+      Some("The walking of people."),
+    ]
+  );
+
+  let TopStatement::EnumStatement { variants, .. } = &top_statements[3] else {
+    panic!("Can not correctly parse an enum statement.");
+  };
+  let variant_docs: Vec<Option<&str>> = variants.iter().map(|(_, _, doc)| doc.as_deref()).collect();
+  assert_eq!(
+    variant_docs,
+    vec![
+      Some("Found nothing."),
+      None,
+      Some("Moved to another place.")
+    ]
+  );
+
+  let TopStatement::StructDeclaration { fields, .. } = &top_statements[4] else {
+    panic!("Can not correctly parse a struct declaration.");
+  };
+  assert_eq!(fields[0].doc(), Some("The full name."));
+  assert_eq!(fields[1].doc(), None);
+
+  let TopStatement::TraitDeclaration { methods, .. } = &top_statements[5] else {
+    panic!("Can not correctly parse a trait declaration.");
+  };
+  assert_eq!(methods[0].0.doc(), Some("Stands still."));
+  assert_eq!(methods[1].0.doc(), None);
+
+  let TopStatement::ImplDeclaration { methods, .. } = &top_statements[6] else {
+    panic!("Can not correctly parse an impl declaration.");
+  };
+  assert_eq!(methods[0].0.doc(), None);
+  assert_eq!(methods[1].0.doc(), Some("Walks slowly."));
+}
+
+#[test]
+fn test_parse_doc_comments_keep_spans() {
+  use crate::core::{
+    parser::{impls::Parser, test::get_span_text},
+    shared::ast::statements::TopStatement,
+  };
+  let source = "/// The answer.\npub fn answer { 42 }\n";
+  let mut parser = Parser::new(source);
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let TopStatement::FunctionDeclaration(func_decl) = &top_statements[0] else {
+    panic!("Can not correctly parse a function declaration.");
+  };
+  // the span of a declaration doesn't cover its doc comments
+  assert_eq!(
+    get_span_text(source, func_decl.span()),
+    "pub fn answer { 42 }"
+  );
+  assert_eq!(func_decl.doc(), Some("The answer."));
+}
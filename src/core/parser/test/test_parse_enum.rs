@@ -0,0 +1,90 @@
+#[test]
+fn test_parse_enum_statement_with_payload_fields() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let mut parser = Parser::new(include_str!("../../../../examples/src/enums.n"));
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let Some(TopStatement::EnumStatement {
+    is_pub, variants, ..
+  }) = top_statements.first()
+  else {
+    panic!("Can not correctly parse an enum statement.");
+  };
+  assert!(*is_pub);
+  assert_eq!(variants.len(), 3);
+  assert!(variants.iter().all(|(_, fields, _)| fields.is_empty()));
+
+  let mut parser = Parser::new(
+    r#"enum ExceptionResponse {
+      ResourceNotFound,
+      NetworkIssue { timeCost, issueMsg, },
+      Unknown { reason }
+    }"#,
+  );
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let Some(TopStatement::EnumStatement {
+    is_pub, variants, ..
+  }) = top_statements.first()
+  else {
+    panic!("Can not correctly parse an enum statement with payload fields.");
+  };
+  assert!(!*is_pub);
+  let variants: Vec<(&str, Vec<&str>)> = variants
+    .iter()
+    .map(|(name, fields, _)| {
+      (
+        name.name.as_str(),
+        fields.iter().map(|field| field.name.as_str()).collect(),
+      )
+    })
+    .collect();
+  assert_eq!(
+    variants,
+    vec![
+      ("ResourceNotFound", vec![]),
+      ("NetworkIssue", vec!["timeCost", "issueMsg"]),
+      ("Unknown", vec!["reason"]),
+    ]
+  );
+}
+
+#[test]
+fn test_parse_enum_statement_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+
+  let mut parser = Parser::new("enum Color { Red, Green, Red { hex }, }");
+  let (top_statements, errors) = parser.parse_program();
+  assert_eq!(top_statements.len(), 1);
+  assert_eq!(errors.len(), 1);
+  if let CompileError::DuplicateEnumVariant { name, pos, .. } = &errors[0] {
+    assert_eq!(name, "Red");
+    assert_eq!(pos.col, 29);
+  } else {
+    panic!("Expected an error about duplicate enum variant.");
+  }
+
+  for (source, expected) in [
+    ("enum Color { Red {} }", "a payload field name"),
+    (
+      "enum Color { Red { r g } }",
+      "a comma ',' or right brace '}' after payload field",
+    ),
+    (
+      "enum Color { Red Green }",
+      "a comma ',' or right brace '}' after enum variant",
+    ),
+  ] {
+    let mut parser = Parser::new(source);
+    let (_, errors) = parser.parse_program();
+    assert!(
+      matches!(
+        errors.first(),
+        Some(CompileError::ExpectedToken { expected: got, .. }) if got == expected
+      ),
+      "{}: {:?}",
+      source,
+      errors
+    );
+  }
+}
@@ -0,0 +1,81 @@
+#[test]
+fn test_parse_function_declaration_params() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let mut parser = Parser::new(include_str!("../../../../examples/src/function_params.n"));
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+
+  let answers: Vec<(&str, Vec<&str>, Option<&str>)> = vec![
+    ("testNoParams", vec![], None),
+    ("testOneParams", vec!["x"], None),
+    ("testTwoParams", vec!["a", "b"], None),
+    ("testOnlyRest", vec![], Some("args")),
+    ("testOneWithRest", vec!["y"], Some("args")),
+    ("testTwoWithRest", vec!["m", "n"], Some("others")),
+  ];
+  assert_eq!(top_statements.len(), answers.len());
+  for (i, (name, params, rest_param)) in answers.into_iter().enumerate() {
+    if let TopStatement::FunctionDeclaration(func_decl) = &top_statements[i] {
+      assert_eq!(func_decl.name().name, name);
+      assert!(!func_decl.is_pub());
+      assert!(!func_decl.is_async());
+      let got_params: Vec<&str> = func_decl
+        .params()
+        .iter()
+        .map(|param| param.name.as_str())
+        .collect();
+      assert_eq!(got_params, params);
+      assert_eq!(
+        func_decl.rest_param().map(|param| param.name.as_str()),
+        rest_param
+      );
+    } else {
+      panic!("Can not correctly parse function \"{}\".", name);
+    }
+  }
+}
+
+#[test]
+fn test_parse_function_declaration_modifiers() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let mut parser = Parser::new("pub async fn fetch(url) { } async fn sleep { } pub fn id { }");
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let answers = [(true, true), (false, true), (true, false)];
+  assert_eq!(top_statements.len(), answers.len());
+  for (i, (is_pub, is_async)) in answers.into_iter().enumerate() {
+    if let TopStatement::FunctionDeclaration(func_decl) = &top_statements[i] {
+      assert_eq!(func_decl.is_pub(), is_pub);
+      assert_eq!(func_decl.is_async(), is_async);
+    } else {
+      panic!("Can not correctly parse a function declaration.");
+    }
+  }
+}
+
+#[test]
+fn test_parse_function_declaration_invalid_params() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::{ast::Position, compile_errors::CompileError},
+  };
+  let mut parser = Parser::new("fn restBeforeOtherParam(...args, i) { }");
+  let (top_statements, errors) = parser.parse_program();
+  assert_eq!(top_statements.len(), 1);
+  assert_eq!(errors.len(), 1);
+  if let CompileError::ParamAfterRestParam { pos } = errors[0] {
+    assert_eq!(pos, Position::new(1, 35, 33, 34));
+  } else {
+    panic!("Expected an error about param after rest param.");
+  }
+
+  let mut parser = Parser::new("fn emptyParentheses() { }");
+  let (top_statements, errors) = parser.parse_program();
+  assert_eq!(top_statements.len(), 1);
+  assert_eq!(errors.len(), 1);
+  if let CompileError::EmptyParenthesesInFunctionDeclaration { pos } = errors[0] {
+    assert_eq!(pos, Position::new(1, 21, 19, 20));
+  } else {
+    panic!("Expected an error about empty parentheses.");
+  }
+}
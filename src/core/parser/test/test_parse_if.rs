@@ -0,0 +1,129 @@
+#[test]
+fn test_parse_if_expression_statement() {
+  use super::stringify_expression;
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::{
+      expressions::{Expression::ExpressionWithBlock, ExpressionWithBlock::IfExpression},
+      statements::Statement,
+    },
+  };
+  let mut parser = Parser::new(
+    r#"{
+      if num > 7 {
+        println("Bigger than expectation.");
+      } else if num < 7 {
+        println("Smaller than expectation.");
+      } else {
+        println("Bingo!");
+      }
+      if ok { done(); }
+    }"#,
+  );
+  let statements = parser.parse_block().expect("Can not parse the block.");
+  assert!(parser.errors.is_empty());
+  assert_eq!(statements.len(), 2);
+
+  if let Statement::ExpressionStatement(
+    ExpressionWithBlock(IfExpression {
+      condition,
+      else_if,
+      else_block,
+      ..
+    }),
+    _,
+  ) = &statements[0]
+  {
+    assert_eq!(stringify_expression(condition), "(num GreaterThan 7)");
+    assert_eq!(else_if.len(), 1);
+    assert_eq!(stringify_expression(&else_if[0].0), "(num LessThan 7)");
+    assert!(else_block.is_some());
+  } else {
+    panic!("Can not correctly parse an if-else expression.");
+  }
+
+  if let Statement::TrailingExpression(ExpressionWithBlock(IfExpression {
+    else_if,
+    else_block,
+    ..
+  })) = &statements[1]
+  {
+    assert!(else_if.is_empty());
+    assert!(else_block.is_none());
+  } else {
+    panic!("Can not correctly parse an if expression without else.");
+  }
+}
+
+#[test]
+fn test_parse_if_expression_as_value() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::{
+      expressions::{
+        Expression, ExpressionWithBlock::BareBlock, ExpressionWithBlock::IfExpression,
+        NormalExpression::IndexExpression, NormalExpression::SimpleLiteral,
+        SimpleLiteral::DecimalLiteral,
+      },
+      statements::Statement,
+    },
+  };
+  let mut parser = Parser::new("arr[ if arr.len() > 3 { 3 } else { 0 } ]");
+  let expr_test = parser.parse_expression();
+  assert!(parser.errors.is_empty());
+  if let Some(Expression::NormalExpression(IndexExpression(_, index, _))) = expr_test {
+    if let Expression::ExpressionWithBlock(IfExpression {
+      then_block,
+      else_block: Some(else_block),
+      ..
+    }) = index.as_ref()
+    {
+      for (block, answer) in [(then_block, "3"), (else_block, "0")] {
+        if let Statement::ExpressionStatement(
+          Expression::ExpressionWithBlock(BareBlock(statements, _)),
+          _,
+        ) = block.as_ref()
+        {
+          assert_eq!(statements.len(), 1);
+          if let Statement::TrailingExpression(Expression::NormalExpression(SimpleLiteral(
+            DecimalLiteral(raw),
+            _,
+          ))) = &statements[0]
+          {
+            assert_eq!(raw, answer);
+          } else {
+            panic!("Can not keep {} as the value of block.", answer);
+          }
+        } else {
+          panic!("Can not correctly parse the block of if expression.");
+        }
+      }
+    } else {
+      panic!("Can not correctly parse an if expression as index.");
+    }
+  } else {
+    panic!("Can not correctly parse to an index expression.");
+  }
+}
+
+#[test]
+fn test_parse_block_trailing_expression() {
+  use super::stringify_expression;
+  use crate::core::{parser::impls::Parser, shared::ast::statements::Statement};
+  let mut parser = Parser::new("{ var x = 1; x + 1 }");
+  let statements = parser.parse_block().expect("Can not parse the block.");
+  assert!(parser.errors.is_empty());
+  assert_eq!(statements.len(), 2);
+  if let Statement::TrailingExpression(expression) = &statements[1] {
+    assert_eq!(stringify_expression(expression), "(x Addition 1)");
+  } else {
+    panic!("Can not keep the trailing expression as the value of block.");
+  }
+
+  // the broken statement is skipped
+  let mut parser = Parser::new("{ x + 1 y }");
+  assert!(parser
+    .parse_block()
+    .is_some_and(|statements| statements.is_empty()));
+  assert_eq!(parser.errors.len(), 1);
+}
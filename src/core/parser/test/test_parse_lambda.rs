@@ -0,0 +1,81 @@
+#[test]
+fn test_parse_lambda_expressions() {
+  use super::stringify_expression;
+  use crate::core::parser::impls::Parser;
+
+  let cases = [
+    ("$: a, b -> a + b", "($: a, b -> (a Addition b))"),
+    ("$: ...args -> args[0]", "($: ...args -> args[0])"),
+    (
+      "$: format, ...args -> f(format, args)",
+      "($: format, ...args -> f(format, args))",
+    ),
+    ("async $: url -> fetch(url)", "(async $: url -> fetch(url))"),
+    ("$: x -> { x + 1 }", "($: x -> (x Addition 1))"),
+    ("$: x -> { log(x); x + 1 }", "($: x -> <block>)"),
+    // the comma after the lambda body belongs to the call arguments
+    (
+      "reduce($: acc, x -> acc + x, 0)",
+      "reduce(($: acc, x -> (acc Addition x)), 0)",
+    ),
+    (
+      "apply($: x -> $: y -> x * y, 2)",
+      "apply(($: x -> ($: y -> (x Multiplication y))), 2)",
+    ),
+  ];
+  for (source, expected) in cases {
+    let mut parser = Parser::new(source);
+    let expr = parser.parse_expression();
+    assert!(parser.errors.is_empty(), "{}: {:?}", source, parser.errors);
+    assert_eq!(
+      stringify_expression(&expr.expect("Can not parse the lambda.")),
+      expected
+    );
+  }
+}
+
+#[test]
+fn test_parse_lambda_in_program() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let mut parser = Parser::new(include_str!("../../../../examples/src/string_utils.n"));
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  assert_eq!(top_statements.len(), 2);
+  assert!(top_statements
+    .iter()
+    .all(|top_statement| matches!(top_statement, TopStatement::FunctionDeclaration(_))));
+}
+
+#[test]
+fn test_parse_lambda_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+
+  let mut parser = Parser::new("$: a b -> a");
+  parser.parse_expression();
+  assert!(matches!(
+    parser.errors.first(),
+    Some(CompileError::ExpectedToken { expected, .. })
+      if expected == "a comma ',' or right arrow '->' after lambda parameter"
+  ));
+
+  let mut parser = Parser::new("$: ...rest, a -> a");
+  parser.parse_expression();
+  assert!(matches!(
+    parser.errors.first(),
+    Some(CompileError::ParamAfterRestParam { .. })
+  ));
+
+  let mut parser = Parser::new("$: a -> ;");
+  parser.parse_expression();
+  assert!(matches!(
+    parser.errors.first(),
+    Some(CompileError::ExpectedExpressionAfterOperator { op_str, .. }) if op_str == "->"
+  ));
+
+  let mut parser = Parser::new("async fetch");
+  parser.parse_expression();
+  assert!(matches!(
+    parser.errors.first(),
+    Some(CompileError::ExpectedToken { expected, .. }) if expected == "lambda label '$:'"
+  ));
+}
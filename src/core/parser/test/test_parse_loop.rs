@@ -0,0 +1,99 @@
+#[test]
+fn test_parse_loop_expressions() {
+  use super::stringify_expression;
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::{
+      expressions::{Expression::ExpressionWithBlock, ExpressionWithBlock::*, ForLoopAlias},
+      statements::Statement,
+    },
+  };
+  let mut parser = Parser::new(
+    r#"{
+      loop { println("forever"); }
+      while i < 3 { printf("loop {}", i); };
+      for n in 0..10 { }
+      for _ in 2 ..= num { }
+      for i, _ in arr { }
+    }"#,
+  );
+  let statements = parser.parse_block().expect("Can not parse the block.");
+  assert!(parser.errors.is_empty());
+  assert_eq!(statements.len(), 5);
+
+  assert!(matches!(
+    &statements[0],
+    Statement::ExpressionStatement(ExpressionWithBlock(LoopExpression { .. }), _)
+  ));
+  if let Statement::ExpressionStatement(ExpressionWithBlock(WhileExpression { condition, .. }), _) =
+    &statements[1]
+  {
+    assert_eq!(stringify_expression(condition), "(i LessThan 3)");
+  } else {
+    panic!("Can not correctly parse a while loop.");
+  }
+
+  let answers = [
+    (None, "n", "(0..10)"),
+    (None, "_", "(2..=num)"),
+    (Some("i"), "_", "arr"),
+  ];
+  let stringify_alias = |alias: &ForLoopAlias| match alias {
+    ForLoopAlias::Named(identifier) => identifier.name.clone(),
+    ForLoopAlias::Anonymous(_) => String::from("_"),
+  };
+  for (i, (index_answer, element_answer, iterable_answer)) in answers.into_iter().enumerate() {
+    // the last loop without semicolon is the trailing expression of the block
+    let (Statement::ExpressionStatement(expression, _) | Statement::TrailingExpression(expression)) =
+      &statements[i + 2]
+    else {
+      panic!("Can not correctly parse a for loop as an expression statement.");
+    };
+    if let ExpressionWithBlock(ForEachExpression {
+      index_var,
+      element_var,
+      iterable,
+      ..
+    }) = expression
+    {
+      assert_eq!(
+        index_var.as_ref().map(stringify_alias),
+        index_answer.map(String::from)
+      );
+      assert_eq!(stringify_alias(element_var), element_answer);
+      assert_eq!(stringify_expression(iterable), iterable_answer);
+    } else {
+      panic!("Can not correctly parse a for loop.");
+    }
+  }
+}
+
+#[test]
+fn test_parse_loop_expressions_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+  let cases = [
+    (
+      "{ for item arr { } }",
+      "keyword 'in' after for loop alias",
+      "arr",
+    ),
+    ("{ while { } }", "a condition expression after 'while'", "{"),
+    ("{ for x in { } }", "an iterable expression after 'in'", "{"),
+  ];
+  for (source, expected_answer, found_answer) in cases {
+    let mut parser = Parser::new(source);
+    assert!(parser
+      .parse_block()
+      .is_some_and(|statements| statements.is_empty()));
+    assert_eq!(parser.errors.len(), 1);
+    if let CompileError::ExpectedToken {
+      expected, found, ..
+    } = &parser.errors[0]
+    {
+      assert_eq!(expected, expected_answer);
+      assert_eq!(found, found_answer);
+    } else {
+      panic!("Expected an error about {}.", expected_answer);
+    }
+  }
+}
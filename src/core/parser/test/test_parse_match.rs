@@ -0,0 +1,146 @@
+#[test]
+fn test_parse_match_expression_patterns() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::{
+      expressions::{
+        Expression, ExpressionWithBlock::MatchExpression, MatchArmPattern, MatchSingleArm,
+        NormalExpression, SimpleLiteral,
+      },
+      statements::Statement,
+    },
+  };
+  let mut parser = Parser::new(
+    r#"match randomNum {
+      1..3 => println("Small"),
+      4 => println("Lucky middle"),
+      5..=6 => println("Bigger"),
+      8 | 9 => println("Bigger than bigger..."),
+      Exception::ResourceNotFound => {
+        printf("Resource {} not found! ({})", resource, debugCode)
+      }
+      other => other
+    }"#,
+  );
+  let expr_test = parser.parse_expression();
+  assert!(parser.errors.is_empty());
+  let Some(Expression::ExpressionWithBlock(MatchExpression { arms, .. })) = expr_test else {
+    panic!("Can not correctly parse to a match expression.");
+  };
+  assert_eq!(arms.len(), 6);
+
+  let decimal_of = |expr: &NormalExpression| match expr {
+    NormalExpression::SimpleLiteral(SimpleLiteral::DecimalLiteral(raw), _) => raw.clone(),
+    _ => panic!("Expected a decimal literal in range pattern."),
+  };
+  for (i, start, end, inclusive) in [(0, "1", "3", false), (2, "5", "6", true)] {
+    if let MatchArmPattern::RangePattern(got_start, got_end, got_inclusive, _) = &arms[i].0 {
+      assert_eq!(decimal_of(got_start), start);
+      assert_eq!(decimal_of(got_end), end);
+      assert_eq!(*got_inclusive, inclusive);
+    } else {
+      panic!("Can not correctly parse a range pattern.");
+    }
+  }
+  assert!(matches!(
+    &arms[1].0,
+    MatchArmPattern::Single(MatchSingleArm::Literal(SimpleLiteral::DecimalLiteral(_), _))
+  ));
+  if let MatchArmPattern::Mutiple(alternatives, _) = &arms[3].0 {
+    assert_eq!(alternatives.len(), 2);
+  } else {
+    panic!("Can not correctly parse alternative patterns.");
+  }
+  if let MatchArmPattern::Single(MatchSingleArm::Path(name_path)) = &arms[4].0 {
+    assert_eq!(
+      name_path.suffix.as_ref().unwrap()[0].name,
+      "ResourceNotFound"
+    );
+  } else {
+    panic!("Can not correctly parse an enum path pattern.");
+  }
+  assert!(matches!(
+    arms[4].1.as_ref(),
+    Statement::ExpressionStatement(Expression::ExpressionWithBlock(_), _)
+  ));
+  assert!(matches!(
+    &arms[5].0,
+    MatchArmPattern::Single(MatchSingleArm::Identifier(_))
+  ));
+}
+
+#[test]
+fn test_parse_match_expression_literals_and_fallback() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::expressions::{
+      Expression, ExpressionWithBlock::MatchExpression, MatchArmPattern, MatchSingleArm,
+      SimpleLiteral,
+    },
+  };
+  let mut parser = Parser::new(
+    r#"{
+      const result = match some_thing {
+        10.01 => println("国庆节的天安门"),
+        0x65e5 => println("日のUnicode"),
+        'ç' => println("cedilla"),
+        "🐂" => println("Michael Jordan"),
+        _ => println("Unknown city"),
+      };
+    }"#,
+  );
+  let statements = parser.parse_block().expect("Can not parse the block.");
+  assert!(parser.errors.is_empty());
+  assert_eq!(statements.len(), 1);
+
+  let mut parser = Parser::new(
+    r#"match some_thing {
+      10.01 => 1,
+      0x65e5 => 2,
+      'ç' => 3,
+      "🐂" => 4,
+      _ => 5,
+    }"#,
+  );
+  let Some(Expression::ExpressionWithBlock(MatchExpression { arms, .. })) =
+    parser.parse_expression()
+  else {
+    panic!("Can not correctly parse to a match expression.");
+  };
+  let answers = [
+    SimpleLiteral::FloatLiteral(String::from("10.01")),
+    SimpleLiteral::HexLiteral(String::from("0x65e5")),
+    SimpleLiteral::CharLiteral(String::from("ç")),
+    SimpleLiteral::StringLiteral(String::from("🐂")),
+  ];
+  assert_eq!(arms.len(), answers.len() + 1);
+  for (i, answer) in answers.iter().enumerate() {
+    if let MatchArmPattern::Single(MatchSingleArm::Literal(literal, _)) = &arms[i].0 {
+      assert_eq!(literal, answer);
+    } else {
+      panic!("Can not correctly parse literal pattern {:?}.", answer);
+    }
+  }
+  assert!(matches!(arms[4].0, MatchArmPattern::Fallback(_)));
+}
+
+#[test]
+fn test_parse_match_expression_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+
+  let mut parser = Parser::new("match x { 1 -> a, }");
+  assert!(parser.parse_expression().is_none());
+  assert_eq!(parser.errors.len(), 1);
+  assert!(matches!(
+    parser.errors[0],
+    CompileError::ExpectedFatArrowAfterMatchPattern { .. }
+  ));
+
+  let mut parser = Parser::new("match x { 1 => a 2 => b }");
+  assert!(parser.parse_expression().is_none());
+  assert_eq!(parser.errors.len(), 1);
+  assert!(matches!(
+    parser.errors[0],
+    CompileError::ExpectedCommaAfterMatchArm { .. }
+  ));
+}
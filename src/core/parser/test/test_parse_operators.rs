@@ -0,0 +1,71 @@
+#[test]
+fn test_parse_expression_binary_precedence() {
+  use super::stringify_expression;
+  use crate::core::parser::impls::Parser;
+  let cases = [
+    (
+      "1 + 2 * 3 - 4",
+      "((1 Addition (2 Multiplication 3)) Subtraction 4)",
+    ),
+    ("2 ** 3 ** 2", "(2 Exponent (3 Exponent 2))"),
+    ("-a ** 2", "((-a) Exponent 2)"),
+    ("!!ok", "(!(!ok))"),
+    ("(1 + 2) * 3", "((1 Addition 2) Multiplication 3)"),
+    ("a << 1 + b", "(a BitwiseShiftLeft (1 Addition b))"),
+    (
+      "a & b ^ c | d",
+      "(((a BitwiseAnd b) BitwiseXor c) BitwiseOr d)",
+    ),
+    (
+      "a || b && c == d | e",
+      "(a LogicalOr (b LogicalAnd (c Equals (d BitwiseOr e))))",
+    ),
+    (
+      "x >= 1 != y < 2",
+      "(((x GreaterThanOrEquals 1) NotEquals y) LessThan 2)",
+    ),
+    ("10 / 2 % 3", "((10 Division 2) Modulo 3)"),
+  ];
+  for (source, answer) in cases {
+    let mut parser = Parser::new(source);
+    let expr = parser
+      .parse_expression()
+      .unwrap_or_else(|| panic!("Can not parse \"{}\" as an expression.", source));
+    assert_eq!(stringify_expression(&expr), answer);
+    assert!(parser.errors.is_empty());
+  }
+}
+
+#[test]
+fn test_parse_expression_range() {
+  use super::stringify_expression;
+  use crate::core::parser::impls::Parser;
+  let cases = [
+    ("0..10", "(0..10)"),
+    ("2 ..= num", "(2..=num)"),
+    ("1..n + 1", "(1..(n Addition 1))"),
+  ];
+  for (source, answer) in cases {
+    let mut parser = Parser::new(source);
+    let expr = parser
+      .parse_expression()
+      .unwrap_or_else(|| panic!("Can not parse \"{}\" as an expression.", source));
+    assert_eq!(stringify_expression(&expr), answer);
+    assert!(parser.errors.is_empty());
+  }
+}
+
+#[test]
+fn test_parse_expression_missing_operand() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+  for (source, op) in [("1 + ", "+"), ("a * -", "-"), ("3..", "..")] {
+    let mut parser = Parser::new(source);
+    assert!(parser.parse_expression().is_none());
+    assert_eq!(parser.errors.len(), 1);
+    if let CompileError::ExpectedExpressionAfterOperator { op_str, .. } = &parser.errors[0] {
+      assert_eq!(op_str, op);
+    } else {
+      panic!("Expected an error about missing operand after \"{}\".", op);
+    }
+  }
+}
@@ -0,0 +1,102 @@
+#[test]
+fn test_parse_expression_postfix_chains() {
+  use super::stringify_expression;
+  use crate::core::parser::impls::Parser;
+  let cases = [
+    ("a.b?.c(1, 2)[i]", "a.b?.c(1, 2)[i]"),
+    ("f()()", "f()()"),
+    (
+      r#"str.split("_").map(capitalized)"#,
+      r#"str.split("_").map(capitalized)"#,
+    ),
+    (
+      "-arr[0] * obj.len()",
+      "((-arr[0]) Multiplication obj.len())",
+    ),
+    ("matrix[i + 1][j]", "matrix[(i Addition 1)][j]"),
+  ];
+  for (source, answer) in cases {
+    let mut parser = Parser::new(source);
+    let expr = parser
+      .parse_expression()
+      .unwrap_or_else(|| panic!("Can not parse \"{}\" as an expression.", source));
+    assert_eq!(stringify_expression(&expr), answer);
+    assert!(parser.errors.is_empty());
+  }
+}
+
+#[test]
+fn test_parse_expression_postfix_positions() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::{
+      ast::expressions::{
+        Expression,
+        NormalExpression::{CallExpression, GetExpression, IndexExpression},
+      },
+      span::{FileId, Span},
+    },
+  };
+  let mut parser = Parser::new("a.b?.c(1, 2)[i]");
+  let expr_test = parser.parse_expression();
+  if let Some(Expression::NormalExpression(IndexExpression(call, _, index_span))) = expr_test {
+    assert_eq!(index_span, Span::new(FileId(0), 0, 15));
+    if let CallExpression(optional_get, arguments, call_span) = call.as_ref() {
+      assert_eq!(arguments.len(), 2);
+      assert_eq!(*call_span, Span::new(FileId(0), 0, 12));
+      if let GetExpression(get, field_c, true, optional_get_span) = optional_get.as_ref() {
+        assert_eq!(field_c.name, "c");
+        assert_eq!(field_c.span, Span::new(FileId(0), 5, 6));
+        assert_eq!(*optional_get_span, Span::new(FileId(0), 0, 6));
+        if let GetExpression(_, field_b, false, get_span) = get.as_ref() {
+          assert_eq!(field_b.name, "b");
+          assert_eq!(field_b.span, Span::new(FileId(0), 2, 3));
+          assert_eq!(*get_span, Span::new(FileId(0), 0, 3));
+        } else {
+          panic!("Can not correctly parse `a.b` to a get expression.");
+        }
+      } else {
+        panic!("Can not correctly parse `?.c` to an optional get expression.");
+      }
+    } else {
+      panic!("Can not correctly parse to a call expression.");
+    }
+  } else {
+    panic!("Can not correctly parse to an index expression.");
+  }
+}
+
+#[test]
+fn test_parse_expression_postfix_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+
+  let mut parser = Parser::new("print(1, 2;");
+  assert!(parser.parse_expression().is_none());
+  assert_eq!(parser.errors.len(), 1);
+  assert!(matches!(
+    parser.errors[0],
+    CompileError::ExpectedCommaOrRightParenthesisAfterArgument { .. }
+  ));
+
+  for (source, dot) in [("a.", "."), ("a?.(1)", "?.")] {
+    let mut parser = Parser::new(source);
+    assert!(parser.parse_expression().is_none());
+    assert_eq!(parser.errors.len(), 1);
+    if let CompileError::ExpectedIdentifierAfterDot { dot_str, .. } = &parser.errors[0] {
+      assert_eq!(dot_str, dot);
+    } else {
+      panic!(
+        "Expected an error about missing field name after \"{}\".",
+        dot
+      );
+    }
+  }
+
+  let mut parser = Parser::new("arr[0;");
+  assert!(parser.parse_expression().is_none());
+  assert_eq!(parser.errors.len(), 1);
+  assert!(matches!(
+    parser.errors[0],
+    CompileError::ExpectedRightBracketAfterIndex { .. }
+  ));
+}
@@ -0,0 +1,108 @@
+#[test]
+fn test_parse_program_top_statements() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::statements::{TopStatement, UseTree},
+  };
+  let mut parser = Parser::new(
+    r#"
+    use std::io as stdio;
+    use crate::fibonacci;
+
+    pub enum HttpStatus { NotFound, Forbidden, }
+
+    struct People {
+      pub name;
+      age;
+    }
+
+    trait Walkable {
+      stand();
+      async walk(distance);
+    }
+
+    impl Walkable for People {
+      stand() { println("I'm standing"); }
+      async walk(distance) { println("I'm walking"); }
+    }
+
+    pub async fn main(args) {
+      stdio.println(args[0]);
+      fibonacci.fib(10);
+    }
+    "#,
+  );
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  assert_eq!(top_statements.len(), 7);
+
+  if let TopStatement::UseStatement(UseTree::Path { path, alias, .. }, ..) = &top_statements[0] {
+    assert_eq!(path.len(), 2);
+    assert_eq!(path[0].name, "std");
+    assert_eq!(path[1].name, "io");
+    assert_eq!(alias.as_ref().unwrap().name, "stdio");
+  } else {
+    panic!("Can not correctly parse a use statement.");
+  }
+  if let TopStatement::EnumStatement {
+    is_pub,
+    name,
+    variants,
+    ..
+  } = &top_statements[2]
+  {
+    assert!(*is_pub);
+    assert_eq!(name.name, "HttpStatus");
+    assert_eq!(variants.len(), 2);
+  } else {
+    panic!("Can not correctly parse an enum statement.");
+  }
+  assert!(matches!(
+    top_statements[3],
+    TopStatement::StructDeclaration { .. }
+  ));
+  assert!(matches!(
+    top_statements[4],
+    TopStatement::TraitDeclaration { .. }
+  ));
+  assert!(matches!(
+    top_statements[5],
+    TopStatement::ImplDeclaration { .. }
+  ));
+  assert!(matches!(
+    top_statements[6],
+    TopStatement::FunctionDeclaration(_)
+  ));
+}
+
+#[test]
+fn test_parse_program_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+
+  let mut parser = Parser::new("fn main { } 42");
+  let (top_statements, errors) = parser.parse_program();
+  assert_eq!(top_statements.len(), 1);
+  assert_eq!(errors.len(), 1);
+  if let CompileError::UnexpectedToken { token_name, .. } = &errors[0] {
+    assert_eq!(token_name, "42");
+  } else {
+    panic!("Expected an error about unexpected token \"42\".");
+  }
+
+  let mut parser = Parser::new("struct People { name }");
+  let (_, errors) = parser.parse_program();
+  assert_eq!(errors.len(), 1);
+  if let CompileError::ExpectedToken { found, .. } = &errors[0] {
+    assert_eq!(found, "}");
+  } else {
+    panic!("Expected an error about missing semicolon after struct field.");
+  }
+
+  // lexing errors are collected as well
+  let mut parser = Parser::new("fn main { (1; ");
+  let (_, errors) = parser.parse_program();
+  assert!(errors.iter().any(|e| matches!(
+    e,
+    CompileError::ExpectedRightParenthesisAfterExpression { .. }
+  )));
+}
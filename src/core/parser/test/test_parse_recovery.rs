@@ -0,0 +1,121 @@
+#[cfg(test)]
+fn parse_broken_source(source: &str) -> (usize, Vec<String>) {
+  use crate::core::parser::impls::Parser;
+  let mut parser = Parser::new(source);
+  let (top_statements, errors) = parser.parse_program();
+  (
+    top_statements.len(),
+    errors.iter().map(|error| error.to_string()).collect(),
+  )
+}
+
+#[test]
+fn test_recover_from_missing_semicolons() {
+  let (top_statements_count, errors) =
+    parse_broken_source(include_str!("broken/missing_semicolons.n"));
+  assert_eq!(top_statements_count, 1);
+  assert_eq!(
+    errors,
+    vec![
+      r#"(Syntax) Expected semicolon ';' after variable declaration but found "var" at line 3:6"#,
+      r#"(Syntax) Expected semicolon ';' after expression but found "println" at line 5:10"#,
+      r#"(Syntax) Expected semicolon ';' after variable declaration but found "b" at line 6:16"#,
+    ]
+  );
+}
+
+#[test]
+fn test_recover_from_broken_top_statements() {
+  let (top_statements_count, errors) =
+    parse_broken_source(include_str!("broken/broken_top_statements.n"));
+  assert_eq!(top_statements_count, 3);
+  assert_eq!(
+    errors,
+    vec![
+      r#"(Syntax) Expected an alias name after 'as' but found ";" at line 1:16"#,
+      r#"(Syntax) Expected semicolon ';' after struct field but found "age" at line 5:6"#,
+      r#"(Syntax) Expected an expression as operand after operator "=" at line 9:10"#,
+      r#"(Syntax) Unexpected token 42 at line 13:3"#,
+      r#"(Syntax) Duplicate enum variant "Red" at line 15:29"#,
+      r#"(Syntax) Expected semicolon ';' after method signature but found "}" at line 17:27"#,
+    ]
+  );
+}
+
+#[test]
+fn test_recover_from_unclosed_blocks() {
+  let (top_statements_count, errors) =
+    parse_broken_source(include_str!("broken/unclosed_blocks.n"));
+  assert_eq!(top_statements_count, 0);
+  assert_eq!(
+    errors,
+    vec![
+      r#"(Syntax) Punctuation "{" is mismatched at line 1:11"#,
+      r#"(Syntax) Expected right brace '}' to end the block but found "end of file" at line 9:1"#,
+    ]
+  );
+}
+
+#[test]
+fn test_recover_from_misplaced_jumps() {
+  let (top_statements_count, errors) =
+    parse_broken_source(include_str!("broken/misplaced_jumps.n"));
+  assert_eq!(top_statements_count, 2);
+  assert_eq!(
+    errors,
+    vec![
+      r#"(Semantic) 'break' is only allowed inside a loop at line 2:8"#,
+      r#"(Semantic) 'continue' is only allowed inside a loop at line 4:29"#,
+      r#"(Syntax) Unexpected token return at line 7:7"#,
+    ]
+  );
+}
+
+#[test]
+fn test_recover_from_malformed_tokens() {
+  let (top_statements_count, errors) =
+    parse_broken_source(include_str!("broken/malformed_tokens.n"));
+  assert_eq!(top_statements_count, 3);
+  assert_eq!(
+    errors,
+    vec![
+      r#"(Syntax) Invalid hexadecimal number format at line 2:13"#,
+      r#"(Syntax) Punctuation "[" is mismatched at line 3:13"#,
+      r#"(Syntax) Punctuation ")" is mismatched at line 3:15"#,
+      r#"(Syntax) Punctuation ")" is mismatched at line 3:16"#,
+      r#"(Syntax) Expected right bracket ']' after index expression at line 3:17"#,
+      r#"(Syntax) Invalid hexadecimal number format at line 6:10"#,
+      r#"(Syntax) Invalid empty char at line 7:20"#,
+      r#"(Syntax) Unclosed char literal at line 7:34"#,
+    ]
+  );
+}
+
+#[test]
+fn test_recover_from_broken_expressions() {
+  let (top_statements_count, errors) =
+    parse_broken_source(include_str!("broken/broken_expressions.n"));
+  assert_eq!(top_statements_count, 1);
+  assert_eq!(
+    errors,
+    vec![
+      r#"(Syntax) Punctuation "(" is mismatched at line 2:12"#,
+      r#"(Syntax) Expected an expression as operand after operator "+" at line 2:15"#,
+      r#"(Syntax) Invalid left-hand side of assignment at line 3:9"#,
+      r#"(Syntax) Invalid left-hand side of assignment at line 4:8"#,
+      r#"(Syntax) Expected a comma ',' after the expression body of match arm at line 5:21"#,
+      r#"(Syntax) Expected a condition expression after 'if' but found "{" at line 6:7"#,
+    ]
+  );
+}
+
+#[test]
+fn test_recover_from_unclosed_comment() {
+  let (top_statements_count, errors) =
+    parse_broken_source(include_str!("broken/unclosed_comment.n"));
+  assert_eq!(top_statements_count, 1);
+  assert_eq!(
+    errors,
+    vec![r#"(Syntax) Unclosed block comment at line 2:3"#]
+  );
+}
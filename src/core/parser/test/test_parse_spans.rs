@@ -0,0 +1,97 @@
+#[test]
+fn test_parse_spans_of_top_statements() {
+  use crate::core::{
+    parser::{impls::Parser, test::get_span_text},
+    shared::ast::statements::TopStatement,
+  };
+  let source = "use std::io;\npub async fn f(a) { return a + 1; }\nstruct Point { x; y; }\n";
+  let mut parser = Parser::new(source);
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let TopStatement::UseStatement(_, _, use_span) = &top_statements[0] else {
+    panic!("Can not correctly parse a use statement.");
+  };
+  assert_eq!(get_span_text(source, *use_span), "use std::io;");
+  let TopStatement::StructDeclaration {
+    span: struct_span, ..
+  } = &top_statements[2]
+  else {
+    panic!("Can not correctly parse a struct declaration.");
+  };
+  assert_eq!(
+    get_span_text(source, *struct_span),
+    "struct Point { x; y; }"
+  );
+
+  let TopStatement::FunctionDeclaration(func_decl) = &top_statements[1] else {
+    panic!("Can not correctly parse a function declaration.");
+  };
+  assert_eq!(
+    get_span_text(source, func_decl.span()),
+    "pub async fn f(a) { return a + 1; }"
+  );
+  assert_eq!(get_span_text(source, func_decl.name().span), "f");
+  assert_eq!(get_span_text(source, func_decl.params()[0].span), "a");
+  assert_eq!(
+    get_span_text(source, func_decl.body()[0].span()),
+    "return a + 1;"
+  );
+}
+
+#[test]
+fn test_parse_spans_of_expressions() {
+  use crate::core::{
+    parser::{impls::Parser, test::get_span_text},
+    shared::ast::expressions::{Expression::NormalExpression, NormalExpression::*},
+  };
+  // the spans are byte offsets, which are not shifted by multi-byte chars
+  let source = "\"星云\" + foo(1, bar[2]) * -x";
+  let mut parser = Parser::new(source);
+  let Some(NormalExpression(BinaryExpression(left, _, right, span))) = parser.parse_expression()
+  else {
+    panic!("Can not correctly parse a binary expression.");
+  };
+  assert!(parser.errors.is_empty());
+  assert_eq!(get_span_text(source, span), source);
+  assert_eq!(get_span_text(source, left.span()), "\"星云\"");
+  assert_eq!(get_span_text(source, right.span()), "foo(1, bar[2]) * -x");
+  let BinaryExpression(call, _, negation, _) = right.as_ref() else {
+    panic!("Can not correctly parse the right operand.");
+  };
+  assert_eq!(get_span_text(source, negation.span()), "-x");
+  let CallExpression(callee, arguments, _) = call.as_ref() else {
+    panic!("Can not correctly parse a call expression.");
+  };
+  assert_eq!(get_span_text(source, callee.span()), "foo");
+  let argument_texts: Vec<&str> = arguments
+    .iter()
+    .map(|argument| get_span_text(source, argument.span()))
+    .collect();
+  assert_eq!(argument_texts, vec!["1", "bar[2]"]);
+}
+
+#[test]
+fn test_parse_spans_of_blocks() {
+  use crate::core::{
+    parser::{impls::Parser, test::get_span_text},
+    shared::ast::statements::{Statement, TopStatement},
+  };
+  let source = "fn main {\n  var i = 0;\n  while i < 3 { i += 1; }\n  i\n}";
+  let mut parser = Parser::new(source);
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let TopStatement::FunctionDeclaration(func_decl) = &top_statements[0] else {
+    panic!("Can not correctly parse a function declaration.");
+  };
+  assert_eq!(get_span_text(source, func_decl.span()), source);
+  let texts: Vec<&str> = func_decl
+    .body()
+    .iter()
+    .map(|statement| get_span_text(source, statement.span()))
+    .collect();
+  assert_eq!(texts, vec!["var i = 0;", "while i < 3 { i += 1; }", "i"]);
+  assert!(matches!(
+    func_decl.body()[2],
+    Statement::TrailingExpression(_)
+  ));
+}
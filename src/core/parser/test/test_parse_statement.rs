@@ -0,0 +1,183 @@
+#[test]
+fn test_parse_statements_in_block() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::{
+      expressions::{Expression, ExpressionWithBlock},
+      statements::{Statement, TopStatement},
+    },
+  };
+  let mut parser = Parser::new(
+    r#"fn search(items, target) {
+      var index = 0;
+      fn matches(a, b) { return a == b; }
+      const found = loop {
+        if index >= items.len() { break; }
+        if !matches(items[index], target) {
+          index += 1;
+          continue;
+        }
+        break index;
+      };
+      if found { found } else { return -1; }
+    }"#,
+  );
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty(), "{:?}", errors);
+  let Some(TopStatement::FunctionDeclaration(function)) = top_statements.first() else {
+    panic!("Can not correctly parse a function declaration.");
+  };
+  let body = function.body();
+  assert_eq!(body.len(), 4);
+  assert!(matches!(body[0], Statement::VariableDeclaration { .. }));
+  let Statement::FunctionDeclaration(inner_function) = &body[1] else {
+    panic!("Can not correctly parse a nested function declaration.");
+  };
+  assert!(matches!(
+    inner_function.body()[0],
+    Statement::ReturnStatement(Some(_), _)
+  ));
+  assert!(matches!(body[2], Statement::VariableDeclaration { .. }));
+  // semicolon is optional after an expression with block
+  assert!(matches!(
+    body[3],
+    Statement::TrailingExpression(Expression::ExpressionWithBlock(
+      ExpressionWithBlock::IfExpression { .. }
+    ))
+  ));
+
+  let mut parser = Parser::new("loop { break; continue; break 1 + 2; }");
+  let Some(Expression::ExpressionWithBlock(ExpressionWithBlock::LoopExpression { block, .. })) =
+    parser.parse_expression()
+  else {
+    panic!("Can not correctly parse a loop expression.");
+  };
+  assert!(parser.errors.is_empty());
+  let Statement::ExpressionStatement(
+    Expression::ExpressionWithBlock(ExpressionWithBlock::BareBlock(statements, _)),
+    _,
+  ) = *block
+  else {
+    panic!("Can not correctly parse the block of loop.");
+  };
+  assert!(matches!(
+    statements.as_slice(),
+    [
+      Statement::BreakStatement(None, _),
+      Statement::ContinueStatement(_),
+      Statement::BreakStatement(Some(_), _),
+    ]
+  ));
+}
+
+#[test]
+fn test_parse_statements_outside_loop_or_function() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+
+  let mut parser = Parser::new("{ break; continue; return 1; }");
+  assert!(parser.parse_block().is_some());
+  assert!(matches!(
+    parser.errors.as_slice(),
+    [
+      CompileError::BreakOutsideLoop { .. },
+      CompileError::ContinueOutsideLoop { .. },
+      CompileError::ReturnOutsideFunction { .. },
+    ]
+  ));
+
+  // the body of lambda or nested function is not a part of outer loop
+  let mut parser = Parser::new(
+    r#"fn main {
+      for item in items {
+        item.map($: x -> { if x { break; } });
+        fn inner { continue; }
+        if item { continue; }
+      }
+    }"#,
+  );
+  let (_, errors) = parser.parse_program();
+  assert_eq!(errors.len(), 2, "{:?}", errors);
+  assert!(matches!(errors[0], CompileError::BreakOutsideLoop { .. }));
+  assert!(matches!(
+    errors[1],
+    CompileError::ContinueOutsideLoop { .. }
+  ));
+}
+
+#[test]
+fn test_parse_statements_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+  for (source, expected) in [
+    ("fn main { a + 1 b }", "semicolon ';' after expression"),
+    (
+      "fn main { return 1 }",
+      "semicolon ';' after return statement",
+    ),
+    (
+      "fn main { loop { continue 1; } }",
+      "semicolon ';' after continue statement",
+    ),
+    (
+      "fn main { loop { break =; } }",
+      "an expression or semicolon ';' after 'break'",
+    ),
+  ] {
+    let mut parser = Parser::new(source);
+    let (_, errors) = parser.parse_program();
+    assert!(
+      errors.iter().any(|e| matches!(
+        e,
+        CompileError::ExpectedToken { expected: got, .. } if got == expected
+      )),
+      "{}: {:?}",
+      source,
+      errors
+    );
+  }
+}
+
+#[test]
+fn test_parse_all_examples() {
+  use crate::core::parser::impls::Parser;
+  for (file_name, contents) in [
+    (
+      "assignment.n",
+      include_str!("../../../../examples/src/assignment.n"),
+    ),
+    (
+      "basic_blocks.n",
+      include_str!("../../../../examples/src/basic_blocks.n"),
+    ),
+    ("enums.n", include_str!("../../../../examples/src/enums.n")),
+    (
+      "fibonacci.n",
+      include_str!("../../../../examples/src/fibonacci.n"),
+    ),
+    (
+      "function_params.n",
+      include_str!("../../../../examples/src/function_params.n"),
+    ),
+    (
+      "match_expr.n",
+      include_str!("../../../../examples/src/match_expr.n"),
+    ),
+    (
+      "string_utils.n",
+      include_str!("../../../../examples/src/string_utils.n"),
+    ),
+    (
+      "struct_and_trait.n",
+      include_str!("../../../../examples/src/struct_and_trait.n"),
+    ),
+    ("use.n", include_str!("../../../../examples/src/use.n")),
+    (
+      "var_decl.n",
+      include_str!("../../../../examples/src/var_decl.n"),
+    ),
+  ] {
+    let mut parser = Parser::new(contents);
+    let (top_statements, errors) = parser.parse_program();
+    assert!(errors.is_empty(), "{}: {:?}", file_name, errors);
+    assert!(!top_statements.is_empty(), "{}", file_name);
+  }
+}
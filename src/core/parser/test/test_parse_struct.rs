@@ -0,0 +1,131 @@
+#[test]
+fn test_parse_struct_declaration_with_optional_fields() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let mut parser = Parser::new(
+    r#"struct People {
+      pub name;
+      pub age;
+      pub desc?;
+
+      weight;
+      height;
+    }"#,
+  );
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let Some(TopStatement::StructDeclaration { name, fields, .. }) = top_statements.first() else {
+    panic!("Can not correctly parse a struct declaration.");
+  };
+  assert_eq!(name.name, "People");
+  let fields_info: Vec<(&str, bool, bool)> = fields
+    .iter()
+    .map(|field| {
+      (
+        field.name().name.as_str(),
+        field.is_pub(),
+        field.is_optional(),
+      )
+    })
+    .collect();
+  assert_eq!(
+    fields_info,
+    vec![
+      ("name", true, false),
+      ("age", true, false),
+      ("desc", true, true),
+      ("weight", false, false),
+      ("height", false, false),
+    ]
+  );
+}
+
+#[test]
+fn test_parse_struct_init_and_new_expressions() {
+  use super::stringify_expression;
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::expressions::{Expression, NormalExpression},
+  };
+
+  let mut parser = Parser::new(
+    r#"People {
+      name = "John",
+      age = 21,
+      weight = 70,
+      height = 178,
+    }"#,
+  );
+  let Some(Expression::NormalExpression(NormalExpression::StructInitExpression(
+    Some(struct_name),
+    fields,
+    _,
+  ))) = parser.parse_expression()
+  else {
+    panic!("Can not correctly parse a struct init expression.");
+  };
+  assert!(parser.errors.is_empty());
+  assert_eq!(struct_name.name, "People");
+  let fields: Vec<(String, String)> = fields
+    .iter()
+    .map(|(name, value)| (name.name.clone(), stringify_expression(value)))
+    .collect();
+  assert_eq!(
+    fields,
+    vec![
+      ("name".to_string(), "\"John\"".to_string()),
+      ("age".to_string(), "21".to_string()),
+      ("weight".to_string(), "70".to_string()),
+      ("height".to_string(), "178".to_string()),
+    ]
+  );
+
+  let mut parser = Parser::new("struct { x = 1, y = Point { x = 2, y = 3 } }");
+  let Some(Expression::NormalExpression(NormalExpression::StructInitExpression(None, fields, _))) =
+    parser.parse_expression()
+  else {
+    panic!("Can not correctly parse an anonymous struct init expression.");
+  };
+  assert!(parser.errors.is_empty());
+  assert_eq!(fields.len(), 2);
+
+  let mut parser = Parser::new("new People");
+  let Some(Expression::NormalExpression(NormalExpression::NewExpression(struct_name, _))) =
+    parser.parse_expression()
+  else {
+    panic!("Can not correctly parse a new expression.");
+  };
+  assert!(parser.errors.is_empty());
+  assert_eq!(struct_name.name, "People");
+}
+
+#[test]
+fn test_parse_struct_init_not_in_block_heads() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::expressions::{Expression, ExpressionWithBlock},
+  };
+  for source in [
+    "if x { }",
+    "while p == q { }",
+    "match status { _ => 1 }",
+    "for i in items { }",
+  ] {
+    let mut parser = Parser::new(source);
+    let expr = parser.parse_expression();
+    assert!(parser.errors.is_empty(), "{}", source);
+    assert!(
+      matches!(expr, Some(Expression::ExpressionWithBlock(_))),
+      "{}",
+      source
+    );
+  }
+
+  // struct init is allowed again inside parentheses of a block head
+  let mut parser = Parser::new("if (p == Point { x = 1 }) { }");
+  let Some(Expression::ExpressionWithBlock(ExpressionWithBlock::IfExpression { .. })) =
+    parser.parse_expression()
+  else {
+    panic!("Can not correctly parse an if expression.");
+  };
+  assert!(parser.errors.is_empty());
+}
@@ -0,0 +1,94 @@
+#[test]
+fn test_parse_trait_and_impl_declarations() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let mut parser = Parser::new(include_str!("../../../../examples/src/struct_and_trait.n"));
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  assert_eq!(top_statements.len(), 5);
+
+  let TopStatement::TraitDeclaration { name, methods, .. } = &top_statements[2] else {
+    panic!("Can not correctly parse a trait declaration.");
+  };
+  assert_eq!(name.name, "Walkable");
+  let methods_info: Vec<(&str, bool, bool, usize)> = methods
+    .iter()
+    .map(|(signature, has_self)| {
+      (
+        signature.name().name.as_str(),
+        signature.is_async(),
+        *has_self,
+        signature.params().len(),
+      )
+    })
+    .collect();
+  assert_eq!(
+    methods_info,
+    vec![
+      ("stand", false, false, 0),
+      ("walk", true, false, 1),
+      ("startRacing", false, true, 0),
+      ("gooseStep", false, true, 1),
+    ]
+  );
+
+  let TopStatement::ImplDeclaration {
+    trait_name: None,
+    struct_name,
+    methods,
+    ..
+  } = &top_statements[3]
+  else {
+    panic!("Can not correctly parse an inherent impl declaration.");
+  };
+  assert_eq!(struct_name.name, "People");
+  let methods_info: Vec<(&str, bool)> = methods
+    .iter()
+    .map(|(decl, has_self)| (decl.name().name.as_str(), *has_self))
+    .collect();
+  assert_eq!(methods_info, vec![("desc", false), ("introduce", true)]);
+
+  let TopStatement::ImplDeclaration {
+    trait_name: Some(trait_name),
+    struct_name,
+    methods,
+    ..
+  } = &top_statements[4]
+  else {
+    panic!("Can not correctly parse a trait impl declaration.");
+  };
+  assert_eq!(trait_name.name, "Walkable");
+  assert_eq!(struct_name.name, "People");
+  assert_eq!(methods.len(), 4);
+  assert!(methods[1].0.is_async());
+  assert_eq!(methods[3].0.params()[0].name, "type");
+}
+
+#[test]
+fn test_parse_trait_and_impl_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+  let cases = [
+    (
+      "trait Walkable { stand() }",
+      "semicolon ';' after method signature",
+    ),
+    (
+      "trait Walkable { walk(distance, self); }",
+      "a parameter name",
+    ),
+    ("fn walk(self) { }", "a parameter name"),
+    ("impl Walkable for { }", "a struct name after 'for'"),
+  ];
+  for (source, expected) in cases {
+    let mut parser = Parser::new(source);
+    let (_, errors) = parser.parse_program();
+    assert!(
+      matches!(
+        errors.first(),
+        Some(CompileError::ExpectedToken { expected: got, .. }) if got == expected
+      ),
+      "{}: {:?}",
+      source,
+      errors
+    );
+  }
+}
@@ -0,0 +1,96 @@
+#[cfg(test)]
+fn stringify_use_tree(use_tree: &crate::core::shared::ast::statements::UseTree) -> String {
+  use crate::core::shared::ast::{statements::UseTree, Identifier};
+  let stringify_path = |path: &Vec<Identifier>| {
+    path
+      .iter()
+      .map(|ident| ident.name.as_str())
+      .collect::<Vec<&str>>()
+      .join("::")
+  };
+  match use_tree {
+    UseTree::Path {
+      path, alias: None, ..
+    } => stringify_path(path),
+    UseTree::Path {
+      path,
+      alias: Some(alias),
+      ..
+    } => format!("{} as {}", stringify_path(path), alias.name),
+    UseTree::Select { path, item, .. } => {
+      format!("{}.{}", stringify_path(path), stringify_use_tree(item))
+    }
+    UseTree::Group { path, children, .. } => {
+      let children = children
+        .iter()
+        .map(stringify_use_tree)
+        .collect::<Vec<String>>()
+        .join(", ");
+      if path.is_empty() {
+        format!("{{ {} }}", children)
+      } else {
+        format!("{} {{ {} }}", stringify_path(path), children)
+      }
+    }
+  }
+}
+
+#[test]
+fn test_parse_use_trees() {
+  use crate::core::{parser::impls::Parser, shared::ast::statements::TopStatement};
+  let mut parser = Parser::new(include_str!("../../../../examples/src/use.n"));
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let use_trees: Vec<String> = top_statements
+    .iter()
+    .map(|top_statement| match top_statement {
+      TopStatement::UseStatement(use_tree, ..) => stringify_use_tree(use_tree),
+      _ => panic!("Expected only use statements."),
+    })
+    .collect();
+  assert_eq!(
+    use_trees,
+    vec![
+      "std::fs",
+      "crate::enums.ExceptionResponse",
+      "{ std::unit_test, std::io as stdio, std::strings { snake_to_camel, camel_to_snake }, crate::fibonacci.fib }",
+    ]
+  );
+
+  let mut parser = Parser::new("use crate::a.b::c { d, e.f as g };");
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  let Some(TopStatement::UseStatement(use_tree, ..)) = top_statements.first() else {
+    panic!("Can not correctly parse a nested use statement.");
+  };
+  assert_eq!(
+    stringify_use_tree(use_tree),
+    "crate::a.b::c { d, e.f as g }"
+  );
+}
+
+#[test]
+fn test_parse_use_errors() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+
+  let mut parser = Parser::new("use { }\nuse std::strings {};");
+  let (top_statements, errors) = parser.parse_program();
+  assert_eq!(top_statements.len(), 2);
+  assert_eq!(errors.len(), 2);
+  assert!(errors
+    .iter()
+    .all(|e| matches!(e, CompileError::EmptyUseTree { .. })));
+
+  let mut parser = Parser::new("use std::strings { snake_to_camel } as strings;");
+  let (top_statements, errors) = parser.parse_program();
+  assert_eq!(top_statements.len(), 1);
+  assert_eq!(errors.len(), 1);
+  assert!(matches!(errors[0], CompileError::AliasOnUseGroup { .. }));
+
+  let mut parser = Parser::new("use crate;");
+  let (_, errors) = parser.parse_program();
+  assert!(matches!(
+    errors.first(),
+    Some(CompileError::ExpectedToken { expected, .. }) if expected == "double colon '::' after 'crate'"
+  ));
+}
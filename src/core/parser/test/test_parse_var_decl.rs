@@ -0,0 +1,107 @@
+#[test]
+fn test_parse_variable_declaration() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::ast::{
+      expressions::ArrayDestructRest,
+      statements::{DeclarationPattern, Statement},
+    },
+  };
+  let mut parser = Parser::new(
+    "{ var a = 1, b = 5.3, d; const [first, second] = f(); var [x, ...[y, ...rest]] = arr; }",
+  );
+  let statements = parser.parse_block().expect("Can not parse the block.");
+  assert!(parser.errors.is_empty());
+  assert_eq!(statements.len(), 3);
+
+  if let Statement::VariableDeclaration {
+    is_const, decls, ..
+  } = &statements[0]
+  {
+    assert!(!is_const);
+    let answers = [("a", true), ("b", true), ("d", false)];
+    assert_eq!(decls.len(), answers.len());
+    for (i, (name, initialized)) in answers.into_iter().enumerate() {
+      if let (DeclarationPattern::Identifier(identifier), initializer) = &decls[i] {
+        assert_eq!(identifier.name, name);
+        assert_eq!(initializer.is_some(), initialized);
+      } else {
+        panic!("Can not correctly parse variable \"{}\".", name);
+      }
+    }
+  } else {
+    panic!("Can not correctly parse a var declaration.");
+  }
+
+  if let Statement::VariableDeclaration {
+    is_const, decls, ..
+  } = &statements[1]
+  {
+    assert!(is_const);
+    if let (DeclarationPattern::Destruct(destruct), Some(_)) = &decls[0] {
+      assert_eq!(destruct.vars.len(), 2);
+      assert!(destruct.rest.is_none());
+    } else {
+      panic!("Can not correctly parse a const destructing declaration.");
+    }
+  } else {
+    panic!("Can not correctly parse a const declaration.");
+  }
+
+  if let Statement::VariableDeclaration { decls, .. } = &statements[2] {
+    if let (DeclarationPattern::Destruct(destruct), Some(_)) = &decls[0] {
+      assert_eq!(destruct.vars[0].name, "x");
+      if let Some(ArrayDestructRest::ChildRest(child)) = &destruct.rest {
+        assert_eq!(child.vars[0].name, "y");
+        if let Some(ArrayDestructRest::Identifier(rest)) = &child.rest {
+          assert_eq!(rest.name, "rest");
+        } else {
+          panic!("Can not correctly parse the nested rest variable.");
+        }
+      } else {
+        panic!("Can not correctly parse the nested destructing.");
+      }
+    } else {
+      panic!("Can not correctly parse a nested destructing declaration.");
+    }
+  } else {
+    panic!("Can not correctly parse a var declaration.");
+  }
+}
+
+#[test]
+fn test_parse_variable_declaration_example() {
+  use crate::core::parser::impls::Parser;
+  let mut parser = Parser::new(include_str!("../../../../examples/src/var_decl.n"));
+  let (top_statements, errors) = parser.parse_program();
+  assert!(errors.is_empty());
+  assert_eq!(top_statements.len(), 2);
+}
+
+#[test]
+fn test_parse_variable_declaration_without_initializer() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::CompileError};
+  let mut parser = Parser::new("{ const a = 1, b; var [c, d]; }");
+  let statements = parser.parse_block();
+  assert!(statements.is_some());
+  assert_eq!(parser.errors.len(), 2);
+  assert!(matches!(
+    parser.errors[0],
+    CompileError::ConstDeclarationWithoutInitializer { .. }
+  ));
+  assert!(matches!(
+    parser.errors[1],
+    CompileError::DestructDeclarationWithoutInitializer { .. }
+  ));
+
+  let mut parser = Parser::new("{ var [a, ...rest, b] = arr; }");
+  assert!(parser
+    .parse_block()
+    .is_some_and(|statements| statements.is_empty()));
+  assert_eq!(parser.errors.len(), 1);
+  if let CompileError::ExpectedToken { found, .. } = &parser.errors[0] {
+    assert_eq!(found, ",");
+  } else {
+    panic!("Expected an error about elements after the rest element.");
+  }
+}
@@ -1,5 +1,5 @@
 use super::statements::Statement;
-use crate::core::shared::ast::{Identifier, Position};
+use crate::core::shared::{ast::Identifier, span::Span};
 
 #[derive(Debug, Clone)]
 pub enum Expression {
@@ -10,113 +10,188 @@ pub enum Expression {
 #[derive(Debug, Clone)]
 pub enum NormalExpression {
   /// A Expression with parenthesis. <br>
-  /// Properties: expression, start, end
-  Grouping(Box<Expression>, Position, Position),
+  /// Properties: expression, span (including the parentheses)
+  Grouping(Box<Expression>, Span),
   /// A simple literal. such as a number, string, etc. <br>
-  /// Properties: literal, token location
-  SimpleLiteral(SimpleLiteral, Position),
+  /// Properties: literal, span
+  SimpleLiteral(SimpleLiteral, Span),
   /// An array literal. such as `[3.14, some_returns(), arr[4]]`. <br>
-  /// Properties: elements, start, end             
-  ArrayLiteral(Vec<Expression>, Position, Position),
+  /// The rest element such as `...rest` in `[first, ...rest]` is only valid
+  /// when the array is the left-hand side of a destructing assignment. <br>
+  /// Properties: elements, rest element, span (including the brackets)
+  ArrayLiteral(Vec<Expression>, Option<Box<NormalExpression>>, Span),
   /// A Path expression.
   /// Usually used to access some fields under a namespace created by `struct` or `enum`. <br>
   /// Examples: `a::b::c`, `HttpStatus::NotFound`, `self::a::b`, `crate::a::b` <br>
   /// Properties: path expression
   NamePathExpression(NamePathExpression),
   /// A lambda expression. Prefix with `$:` <br>
-  /// Examples: `$: a, b -> a + b` <br>
+  /// Examples: `$: a, b -> a + b`, `async $: url -> { await fetch(url) }`, `$: ...args -> args` <br>
   LambdaExpression(LambdaExpression),
+  /// A Struct init expression. <br>
+  /// Examples: `People { name = "John", age = 21, }`, `struct { x = 1, y = 2 }` <br>
+  /// Properties: struct name (`None` for anonymous struct), fields with initial values, span
+  StructInitExpression(Option<Identifier>, Vec<(Identifier, Expression)>, Span),
+  /// A New expression, creates a struct without initializing its fields. <br>
+  /// Examples: `new People` <br>
+  /// Properties: struct name, span
+  NewExpression(Identifier, Span),
   /// A Await expression. Prefix with `await`. <br>
   /// Examples: `await a`, `await a()` <br>
-  /// Properties: expression returns a `Promise`
-  AwaitExpression(Box<Expression>),
+  /// Properties: expression returns a `Promise`, span
+  AwaitExpression(Box<Expression>, Span),
   /// A Get expression. <br>
   /// Examples: `a.b` <br>
-  /// Properties: source, field, optional
-  GetExpression(Box<NormalExpression>, Identifier, bool),
+  /// Properties: source, field, optional, span
+  GetExpression(Box<NormalExpression>, Identifier, bool, Span),
   /// A Call expression. <br>
   /// Examples: `a()`, `a(1, 2, 3)` <br>
-  /// Properties: source, arguments
-  CallExpression(Box<NormalExpression>, Vec<NormalExpression>),
+  /// Properties: source, arguments, span
+  CallExpression(Box<NormalExpression>, Vec<Expression>, Span),
   /// A Index expression. <br>
   /// Examples: `a[some_var]`, `a[1 + 2]` <br>
-  /// Properties: source, index
-  IndexExpression(Box<NormalExpression>, Box<NormalExpression>),
+  /// Properties: source, index, span
+  IndexExpression(Box<NormalExpression>, Box<Expression>, Span),
   /// A Unary expression. <br>
   /// Examples: `-a`, `!a` <br>
-  /// Properties: expression, unary operator
-  UnaryExpression(Box<NormalExpression>, UnaryOperator),
+  /// Properties: expression, unary operator, span
+  UnaryExpression(Box<NormalExpression>, UnaryOperator, Span),
   /// A Binary expression. <br>
   /// Examples: `a + b`, `a * b` <br>
-  /// Properties: left hand, binary operator, right hand
-  BinaryExpression(Box<NormalExpression>, BinaryOperator, Box<NormalExpression>),
+  /// Properties: left hand, binary operator, right hand, span
+  BinaryExpression(
+    Box<NormalExpression>,
+    BinaryOperator,
+    Box<NormalExpression>,
+    Span,
+  ),
   /// A Assignment expression. <br>
   /// Examples: `a = 1`, `a.b = 1`, `a[0] = 1`, `[a, b] = [1, 2]` <br>
-  AssignmentExpression(AssignmentLeftHand, Box<Expression>),
+  /// Properties: left hand, right hand, span
+  AssignmentExpression(AssignmentLeftHand, Box<Expression>, Span),
   /// A Compound assignment expression. <br>
   /// Examples: `a += 1`, `a -= 1`, `a *= 1`, `a /= 1`, `a %= 1`, <br>
   /// `a **= 1`, `a &= 1`, `a |= 1`, `a ^= 1`, <br>
   /// `a <<= 1`, `a >>= 1`, `a &&= 1`, `a ||= 1` <br>
-  /// Properties: left hand, compound assignment operator, right hand
+  /// Properties: left hand, compound assignment operator, right hand, span
   CompoundAssignmentExpression(
     AssignmentLeftHand,
     CompoundAssignmentOperator,
     Box<Expression>,
+    Span,
   ),
   /// A Range expression. <br>
   /// Examples: `1..5`, `1..=5` <br>
-  /// Properties: start, end, inclusive
-  RangeExpression(Box<NormalExpression>, Box<NormalExpression>, bool),
+  /// Properties: start, end, inclusive, span
+  RangeExpression(Box<NormalExpression>, Box<NormalExpression>, bool, Span),
+}
+impl NormalExpression {
+  pub fn span(&self) -> Span {
+    match self {
+      NormalExpression::Grouping(_, span)
+      | NormalExpression::SimpleLiteral(_, span)
+      | NormalExpression::ArrayLiteral(_, _, span)
+      | NormalExpression::StructInitExpression(_, _, span)
+      | NormalExpression::NewExpression(_, span)
+      | NormalExpression::AwaitExpression(_, span)
+      | NormalExpression::GetExpression(_, _, _, span)
+      | NormalExpression::CallExpression(_, _, span)
+      | NormalExpression::IndexExpression(_, _, span)
+      | NormalExpression::UnaryExpression(_, _, span)
+      | NormalExpression::BinaryExpression(_, _, _, span)
+      | NormalExpression::AssignmentExpression(_, _, span)
+      | NormalExpression::CompoundAssignmentExpression(_, _, _, span)
+      | NormalExpression::RangeExpression(_, _, _, span) => *span,
+      NormalExpression::NamePathExpression(name_path) => name_path.span,
+      NormalExpression::LambdaExpression(lambda) => lambda.span,
+    }
+  }
+}
+impl Expression {
+  pub fn span(&self) -> Span {
+    match self {
+      Expression::NormalExpression(normal_expression) => normal_expression.span(),
+      Expression::ExpressionWithBlock(block_expression) => block_expression.span(),
+    }
+  }
 }
 
-/// Tips: We will likely use a `Box<Statement>` to represent a block.
-///
-/// Because we allow these kinds of with-block-expressions to only contain a single statement.
+/// Tips: We use a `Box<Statement>` to represent a block.
 ///
-/// So it maybe one single statement, or a direct block expression.
+/// The block is always wrapped as a bare block inside an expression statement,
+/// and its trailing expression without semicolon is kept as the value of the block.
 ///
 /// ```txt
-/// - { some_expresion } => Box<Statement> =>
-///   Box<ExpressionStatement(
-///     Expression::Normal(
-///       NormalExpression
-///     )
-///   )>
-/// - { expr1; expr2; some_expr_with_block expr3; } => Box<Statement> =>
+/// - { expr1; expr2 } => Box<Statement> =>
 ///   Box<ExpressionStatement(
 ///     Expression::ExpressionWithBlock(
-///       ExpressionWithBlock::SingleBlock(
-///         Vec<Statement>
-///       )
+///       ExpressionWithBlock::BareBlock(vec![
+///         ExpressionStatement(expr1),
+///         TrailingExpression(expr2),
+///       ])
 ///     )
 ///   )>
+/// - { expr1; expr2; } => the same as above, but the last one is also an `ExpressionStatement`,
+///   so the block has no value.
 /// ```
 ///
 
 #[derive(Debug, Clone)]
 pub enum ExpressionWithBlock {
-  BareBlock(Vec<Statement>),
+  /// Properties: statements, span (including the braces)
+  BareBlock(Vec<Statement>, Span),
   IfExpression {
     condition: Box<Expression>,
     then_block: Box<Statement>,
     else_if: Vec<(Box<Expression>, Box<Statement>)>,
     else_block: Option<Box<Statement>>,
+    span: Span,
   },
+  /// An infinite loop, can only be stopped by `break` or `return`. <br>
+  /// Examples: `loop { ... }`
+  LoopExpression { block: Box<Statement>, span: Span },
   WhileExpression {
     condition: Box<Expression>,
     block: Box<Statement>,
+    span: Span,
   },
+  /// Examples: `for item in arr { ... }`, `for i, _ in arr { ... }` <br>
+  /// With a single alias, it's bound to the element, otherwise the first one is the index.
   ForEachExpression {
-    index_var: Identifier,
-    element_var: Identifier,
+    index_var: Option<ForLoopAlias>,
+    element_var: ForLoopAlias,
     iterable: Box<Expression>,
     block: Box<Statement>,
+    span: Span,
   },
+  /// Examples: `match x { 1 | 2 => "small", 3..=9 => { "big" } _ => "unknown" }` <br>
+  /// An arm's body is either a block, or a single expression inside an `ExpressionStatement`.
   MatchExpression {
     expression: Box<Expression>,
-    arms: Vec<(Vec<MatchArmPattern>, Box<Statement>)>,
+    arms: Vec<(MatchArmPattern, Box<Statement>)>,
+    span: Span,
   },
 }
+impl ExpressionWithBlock {
+  pub fn span(&self) -> Span {
+    match self {
+      ExpressionWithBlock::BareBlock(_, span)
+      | ExpressionWithBlock::IfExpression { span, .. }
+      | ExpressionWithBlock::LoopExpression { span, .. }
+      | ExpressionWithBlock::WhileExpression { span, .. }
+      | ExpressionWithBlock::ForEachExpression { span, .. }
+      | ExpressionWithBlock::MatchExpression { span, .. } => *span,
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub enum ForLoopAlias {
+  /// Binding the index or element to a variable.
+  Named(Identifier),
+  /// The anonymous alias `_`, ignores the index or element.
+  Anonymous(Span),
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SimpleLiteral {
@@ -134,22 +209,37 @@ pub enum SimpleLiteral {
 #[derive(Debug, Clone)]
 pub enum NamePathHead {
   Identifier(Identifier),
-  SelfSymbol(Position),
-  BigSelfSymbol(Position),
-  CrateSymbol(Position),
+  SelfSymbol(Span),
+  BigSelfSymbol(Span),
+  CrateSymbol(Span),
+}
+impl NamePathHead {
+  pub fn span(&self) -> Span {
+    match self {
+      NamePathHead::Identifier(identifier) => identifier.span,
+      NamePathHead::SelfSymbol(span)
+      | NamePathHead::BigSelfSymbol(span)
+      | NamePathHead::CrateSymbol(span) => *span,
+    }
+  }
 }
 
 #[derive(Debug, Clone)]
 pub struct NamePathExpression {
   pub head: NamePathHead,
   pub suffix: Option<Vec<Identifier>>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct LambdaExpression {
   pub is_async: bool,
   pub params: Vec<Identifier>,
+  pub rest_param: Option<Identifier>,
+  /// A single expression body is stored as a trailing expression,
+  /// so both `$: x -> x + 1` and `$: x -> { x + 1 }` have the same body.
   pub body: Vec<Statement>,
+  pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -228,6 +318,8 @@ pub struct ArrayDestructAssign {
   /// Deconstructing the rest of parent array into a single variable,
   /// or starting another array destructing right after.
   pub rest: Option<ArrayDestructRest>,
+  /// Including the brackets.
+  pub span: Span,
 }
 
 #[derive(Debug, Clone)]
@@ -237,7 +329,7 @@ pub enum ArrayDestructRest {
   /// Maybe another array destructing.
   ///
   /// Example: `[a, b, ...[c, d]]`
-  ChildRest(Box<ArrayDestructRest>),
+  ChildRest(Box<ArrayDestructAssign>),
 }
 
 #[derive(Debug, Clone)]
@@ -247,18 +339,29 @@ pub enum MatchArmPattern {
   Single(MatchSingleArm),
   /// Mutiple patterns. <br>
   /// Examples: `11.6 | 10.21` <br>
-  Mutiple(Vec<MatchSingleArm>),
+  /// Properties: alternatives, span
+  Mutiple(Vec<MatchSingleArm>, Span),
   /// A Range pattern. <br>
   /// Examples: `1..5`, `1..=5` <br>
-  RangePattern(Box<NormalExpression>, Box<NormalExpression>, bool),
+  /// Properties: start, end, inclusive, span
+  RangePattern(Box<NormalExpression>, Box<NormalExpression>, bool, Span),
   /// Fallback pattern. <br>
   /// Examples: `_` <br>
-  Fallback(Position),
+  Fallback(Span),
 }
 
 #[derive(Debug, Clone)]
 pub enum MatchSingleArm {
-  Literal(SimpleLiteral),
+  Literal(SimpleLiteral, Span),
   Identifier(Identifier),
   Path(NamePathExpression),
 }
+impl MatchSingleArm {
+  pub fn span(&self) -> Span {
+    match self {
+      MatchSingleArm::Literal(_, span) => *span,
+      MatchSingleArm::Identifier(identifier) => identifier.span,
+      MatchSingleArm::Path(name_path) => name_path.span,
+    }
+  }
+}
@@ -1,22 +1,35 @@
 use std::fmt::{Display, Formatter};
 
+use super::span::Span;
+
 pub mod expressions;
 pub mod statements;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Identifier {
   pub name: String,
-  pub pos: Position,
+  pub span: Span,
 }
 
+/// The position of a token or a compile error, AST nodes use `Span` instead.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Position {
+  // Human readable position, the cursor right after the token
   pub line: usize,
   pub col: usize,
+
+  // byte offsets of the token in source, the span is `start..end`
+  pub start: usize,
+  pub end: usize,
 }
 impl Position {
-  pub fn new(line: usize, col: usize) -> Position {
-    Position { line, col }
+  pub fn new(line: usize, col: usize, start: usize, end: usize) -> Position {
+    Position {
+      line,
+      col,
+      start,
+      end,
+    }
   }
 }
 impl Display for Position {
@@ -1,47 +1,142 @@
-use super::{expressions::Expression, Identifier};
+use super::{
+  expressions::{ArrayDestructAssign, Expression},
+  Identifier,
+};
+use crate::core::shared::span::Span;
 
+/// The span of a statement includes its semicolon.
 #[derive(Debug, Clone)]
 pub enum Statement {
-  ExpressionStatement(Expression),
+  ExpressionStatement(Expression, Span),
+  /// The last expression of a block without semicolon, its value is the value of the block.
+  ///
+  /// Example: `x + 1` in `{ var x = 1; x + 1 }`
+  TrailingExpression(Expression),
   VariableDeclaration {
     is_const: bool,
-    decls: Vec<(Identifier, Option<Expression>)>,
+    decls: Vec<(DeclarationPattern, Option<Expression>)>,
+    span: Span,
   },
-  ReturnStatement(Option<Expression>),
-  BreakStatement(Option<Expression>),
-  ContinueStatement,
+  ReturnStatement(Option<Expression>, Span),
+  BreakStatement(Option<Expression>, Span),
+  ContinueStatement(Span),
   FunctionDeclaration(FunctionDeclaration),
 }
+impl Statement {
+  pub fn span(&self) -> Span {
+    match self {
+      Statement::ExpressionStatement(_, span)
+      | Statement::VariableDeclaration { span, .. }
+      | Statement::ReturnStatement(_, span)
+      | Statement::BreakStatement(_, span)
+      | Statement::ContinueStatement(span) => *span,
+      Statement::TrailingExpression(expression) => expression.span(),
+      Statement::FunctionDeclaration(function_decl) => function_decl.span(),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+pub enum DeclarationPattern {
+  /// Declaring a single variable.
+  ///
+  /// Example: `var a = 1;`
+  Identifier(Identifier),
+  /// Deconstructing an array into several variables, must be initialized.
+  ///
+  /// Example: `const [a, b, ...[c, ...rest]] = arr;`
+  Destruct(ArrayDestructAssign),
+}
 
+/// The span of a top statement includes its modifiers, such as `pub` and `async`.
+///
+/// The doc of a declaration is the text of the doc comments (`///` or `/** */`) right before it,
+/// without the comment markers, and the lines of several comments are joined by `\n`.
 #[derive(Debug, Clone)]
 pub enum TopStatement {
-  UseStatement(Vec<UseEntry>),
+  /// Properties: use tree, doc, span (including the keyword `use` and the semicolon)
+  UseStatement(UseTree, Option<String>, Span),
   EnumStatement {
+    is_pub: bool,
     name: Identifier,
-    variants: Vec<Identifier>,
+    /// Properties: variant name, payload fields (empty for unit variant), doc <br>
+    /// Example: `NetworkIssue { timeCost, issueMsg }`
+    variants: Vec<(Identifier, Vec<Identifier>, Option<String>)>,
+    doc: Option<String>,
+    span: Span,
   },
   FunctionDeclaration(FunctionDeclaration),
   StructDeclaration {
     name: Identifier,
     fields: Vec<StructField>,
+    doc: Option<String>,
+    span: Span,
   },
   TraitDeclaration {
     name: Identifier,
-    methods: Vec<FunctionSignature>,
+    /// Properties: method signature, is member method (takes `self` as the first parameter)
+    methods: Vec<(FunctionSignature, bool)>,
+    doc: Option<String>,
+    span: Span,
   },
   ImplDeclaration {
-    trait_name: Identifier,
+    /// `None` for inherent impl, such as `impl People { }`
+    trait_name: Option<Identifier>,
     struct_name: Identifier,
-    /// Properties: method implementation, is member method
+    /// Properties: method implementation, is member method (takes `self` as the first parameter)
     methods: Vec<(FunctionDeclaration, bool)>,
+    doc: Option<String>,
+    span: Span,
   },
 }
+impl TopStatement {
+  pub fn doc(&self) -> Option<&str> {
+    match self {
+      TopStatement::UseStatement(_, doc, _)
+      | TopStatement::EnumStatement { doc, .. }
+      | TopStatement::StructDeclaration { doc, .. }
+      | TopStatement::TraitDeclaration { doc, .. }
+      | TopStatement::ImplDeclaration { doc, .. } => doc.as_deref(),
+      TopStatement::FunctionDeclaration(function_decl) => function_decl.doc(),
+    }
+  }
+
+  /// Attaches the doc comments which are collected before the modifiers of the statement.
+  pub fn with_doc(mut self, new_doc: Option<String>) -> TopStatement {
+    match &mut self {
+      TopStatement::UseStatement(_, doc, _)
+      | TopStatement::EnumStatement { doc, .. }
+      | TopStatement::StructDeclaration { doc, .. }
+      | TopStatement::TraitDeclaration { doc, .. }
+      | TopStatement::ImplDeclaration { doc, .. } => *doc = new_doc,
+      TopStatement::FunctionDeclaration(function_decl) => function_decl.doc = new_doc,
+    }
+    self
+  }
+}
 
 #[derive(Debug, Clone)]
-pub struct UseEntry {
-  pub path: Vec<Identifier>,
-  pub name: Identifier,
-  pub alias: Option<Identifier>,
+pub enum UseTree {
+  /// Examples: `std::fs`, `std::io as stdio`
+  Path {
+    path: Vec<Identifier>,
+    alias: Option<Identifier>,
+    span: Span,
+  },
+  /// Selects an item from a module with `.` <br>
+  /// Examples: `crate::enums.ExceptionResponse`, `crate::fibonacci.fib as fibonacci`
+  Select {
+    path: Vec<Identifier>,
+    item: Box<UseTree>,
+    span: Span,
+  },
+  /// The path is empty for the top group, such as `use { std::fs, std::io }` <br>
+  /// Examples: `std::strings { snake_to_camel, camel_to_snake }`
+  Group {
+    path: Vec<Identifier>,
+    children: Vec<UseTree>,
+    span: Span,
+  },
 }
 
 #[derive(Debug, Clone)]
@@ -50,14 +145,100 @@ pub struct FunctionDeclaration {
   is_async: bool,
   name: Identifier,
   params: Vec<Identifier>,
+  /// The rest parameter collects all remaining arguments, must be the last one.
+  ///
+  /// Example: `args` in `fn print(format, ...args) { }`
+  rest_param: Option<Identifier>,
   body: Vec<Statement>,
+  doc: Option<String>,
+  span: Span,
+}
+impl FunctionDeclaration {
+  pub fn new(
+    is_pub: bool,
+    is_async: bool,
+    name: Identifier,
+    params: Vec<Identifier>,
+    rest_param: Option<Identifier>,
+    body: Vec<Statement>,
+    span: Span,
+  ) -> FunctionDeclaration {
+    FunctionDeclaration {
+      is_pub,
+      is_async,
+      name,
+      params,
+      rest_param,
+      body,
+      doc: None,
+      span,
+    }
+  }
+  pub fn with_doc(mut self, doc: Option<String>) -> FunctionDeclaration {
+    self.doc = doc;
+    self
+  }
+  pub fn is_pub(&self) -> bool {
+    self.is_pub
+  }
+  pub fn is_async(&self) -> bool {
+    self.is_async
+  }
+  pub fn name(&self) -> &Identifier {
+    &self.name
+  }
+  pub fn params(&self) -> &Vec<Identifier> {
+    &self.params
+  }
+  pub fn rest_param(&self) -> Option<&Identifier> {
+    self.rest_param.as_ref()
+  }
+  pub fn body(&self) -> &Vec<Statement> {
+    &self.body
+  }
+  pub fn doc(&self) -> Option<&str> {
+    self.doc.as_deref()
+  }
+  pub fn span(&self) -> Span {
+    self.span
+  }
 }
 
 #[derive(Debug, Clone)]
 pub struct StructField {
   name: Identifier,
   is_pub: bool,
-  is_const: bool,
+  /// An optional field is declared with a question mark, such as `desc?;`
+  is_optional: bool,
+  doc: Option<String>,
+  span: Span,
+}
+impl StructField {
+  pub fn new(name: Identifier, is_pub: bool, is_optional: bool, span: Span) -> StructField {
+    StructField {
+      name,
+      is_pub,
+      is_optional,
+      doc: None,
+      span,
+    }
+  }
+  pub fn with_doc(mut self, doc: Option<String>) -> StructField {
+    self.doc = doc;
+    self
+  }
+  pub fn name(&self) -> &Identifier {
+    &self.name
+  }
+  pub fn is_pub(&self) -> bool {
+    self.is_pub
+  }
+  pub fn is_optional(&self) -> bool {
+    self.is_optional
+  }
+  pub fn doc(&self) -> Option<&str> {
+    self.doc.as_deref()
+  }
 }
 
 #[derive(Debug, Clone)]
@@ -66,4 +247,49 @@ pub struct FunctionSignature {
   is_async: bool,
   name: Identifier,
   params: Vec<Identifier>,
+  rest_param: Option<Identifier>,
+  doc: Option<String>,
+  span: Span,
+}
+impl FunctionSignature {
+  pub fn new(
+    is_pub: bool,
+    is_async: bool,
+    name: Identifier,
+    params: Vec<Identifier>,
+    rest_param: Option<Identifier>,
+    span: Span,
+  ) -> FunctionSignature {
+    FunctionSignature {
+      is_pub,
+      is_async,
+      name,
+      params,
+      rest_param,
+      doc: None,
+      span,
+    }
+  }
+  pub fn with_doc(mut self, doc: Option<String>) -> FunctionSignature {
+    self.doc = doc;
+    self
+  }
+  pub fn is_pub(&self) -> bool {
+    self.is_pub
+  }
+  pub fn is_async(&self) -> bool {
+    self.is_async
+  }
+  pub fn name(&self) -> &Identifier {
+    &self.name
+  }
+  pub fn params(&self) -> &Vec<Identifier> {
+    &self.params
+  }
+  pub fn rest_param(&self) -> Option<&Identifier> {
+    self.rest_param.as_ref()
+  }
+  pub fn doc(&self) -> Option<&str> {
+    self.doc.as_deref()
+  }
 }
@@ -2,11 +2,17 @@ use thiserror::Error;
 
 use super::ast::Position;
 
+mod test;
+
 #[derive(Debug, Error, Clone)]
 pub enum CompileError {
   // Lexer Errors:
   #[error("(Syntax) Punctuation \"{punc_str}\" is mismatched at {pos}")]
-  ImbalancedPair { punc_str: String, pos: Position },
+  ImbalancedPair {
+    punc_str: String,
+    pos: Position,
+    open_pos: Option<Position>,
+  },
 
   #[error("(Syntax) Invalid {numeric_type} number format at {pos}")]
   InvalidFormatNumber { numeric_type: String, pos: Position },
@@ -17,6 +23,9 @@ pub enum CompileError {
   #[error("(Syntax) Unclosed char literal at {pos}")]
   UnclosedCharLiteral { pos: Position },
 
+  #[error("(Syntax) Unclosed block comment at {pos}")]
+  UnclosedBlockComment { pos: Position },
+
   // Parser Errors:
   #[error("(Syntax) Unexpected token {token_name} at {pos}")]
   UnexpectedToken { token_name: String, pos: Position },
@@ -32,4 +41,195 @@ pub enum CompileError {
 
   #[error("(Syntax) Expected an identifier after double colon in name path expression at {pos}")]
   ExpectedIdentifierAfterDoubleColon { pos: Position },
+
+  #[error("(Syntax) Expected an expression as operand after operator \"{op_str}\" at {pos}")]
+  ExpectedExpressionAfterOperator { op_str: String, pos: Position },
+
+  #[error("(Syntax) Expected a comma ',' to seperate or a right parenthesis ')' to terminate in call arguments at {pos}")]
+  ExpectedCommaOrRightParenthesisAfterArgument { pos: Position },
+
+  #[error("(Syntax) Expected an identifier as field name after \"{dot_str}\" at {pos}")]
+  ExpectedIdentifierAfterDot { dot_str: String, pos: Position },
+
+  #[error("(Syntax) Expected an index expression after left bracket '[' at {pos}")]
+  ExpectedExpressionAfterLeftBracket { pos: Position },
+
+  #[error("(Syntax) Expected right bracket ']' after index expression at {pos}")]
+  ExpectedRightBracketAfterIndex { pos: Position },
+
+  #[error(
+    "(Syntax) Rest parameter must be the last parameter, but found another one after it at {pos}"
+  )]
+  ParamAfterRestParam { pos: Position },
+
+  #[error("(Syntax) Empty parentheses '()' are forbidden in function declaration, remove them if there's no parameter at {pos}")]
+  EmptyParenthesesInFunctionDeclaration { pos: Position },
+
+  #[error("(Syntax) Constant declaration must be initialized with '=' at {pos}")]
+  ConstDeclarationWithoutInitializer { pos: Position },
+
+  #[error("(Syntax) Array destructuring declaration must be initialized with '=' at {pos}")]
+  DestructDeclarationWithoutInitializer { pos: Position },
+
+  #[error("(Syntax) Expected a fat arrow '=>' after match pattern at {pos}")]
+  ExpectedFatArrowAfterMatchPattern { pos: Position },
+
+  #[error("(Syntax) Expected a comma ',' after the expression body of match arm at {pos}")]
+  ExpectedCommaAfterMatchArm { pos: Position },
+
+  #[error("(Syntax) Invalid left-hand side of assignment at {pos}")]
+  InvalidAssignmentTarget { pos: Position },
+
+  #[error("(Syntax) Empty use tree at {pos}")]
+  EmptyUseTree { pos: Position },
+
+  #[error("(Syntax) A group of use tree can not be aliased at {pos}")]
+  AliasOnUseGroup { pos: Position },
+
+  #[error("(Syntax) Duplicate enum variant \"{name}\" at {pos}")]
+  DuplicateEnumVariant {
+    name: String,
+    pos: Position,
+    first_pos: Position,
+  },
+
+  #[error("(Semantic) 'break' is only allowed inside a loop at {pos}")]
+  BreakOutsideLoop { pos: Position },
+
+  #[error("(Semantic) 'continue' is only allowed inside a loop at {pos}")]
+  ContinueOutsideLoop { pos: Position },
+
+  #[error("(Semantic) 'return' is only allowed inside a function at {pos}")]
+  ReturnOutsideFunction { pos: Position },
+
+  #[error("(Syntax) Expected {expected} but found \"{found}\" at {pos}")]
+  ExpectedToken {
+    expected: String,
+    found: String,
+    pos: Position,
+  },
+}
+
+impl CompileError {
+  /// The position where the error occurs, which is the primary span in diagnostics.
+  pub fn get_pos(&self) -> Position {
+    match self {
+      CompileError::ImbalancedPair { pos, .. }
+      | CompileError::InvalidFormatNumber { pos, .. }
+      | CompileError::InvalidEmptyChar { pos, .. }
+      | CompileError::UnclosedCharLiteral { pos, .. }
+      | CompileError::UnclosedBlockComment { pos, .. }
+      | CompileError::UnexpectedToken { pos, .. }
+      | CompileError::ExpectedExpressionAfterLeftParenthesis { pos, .. }
+      | CompileError::ExpectedRightParenthesisAfterExpression { pos, .. }
+      | CompileError::ExpectedCommaOrRightBracketAfterExpression { pos, .. }
+      | CompileError::ExpectedIdentifierAfterDoubleColon { pos, .. }
+      | CompileError::ExpectedExpressionAfterOperator { pos, .. }
+      | CompileError::ExpectedCommaOrRightParenthesisAfterArgument { pos, .. }
+      | CompileError::ExpectedIdentifierAfterDot { pos, .. }
+      | CompileError::ExpectedExpressionAfterLeftBracket { pos, .. }
+      | CompileError::ExpectedRightBracketAfterIndex { pos, .. }
+      | CompileError::ParamAfterRestParam { pos, .. }
+      | CompileError::EmptyParenthesesInFunctionDeclaration { pos, .. }
+      | CompileError::ConstDeclarationWithoutInitializer { pos, .. }
+      | CompileError::DestructDeclarationWithoutInitializer { pos, .. }
+      | CompileError::ExpectedFatArrowAfterMatchPattern { pos, .. }
+      | CompileError::ExpectedCommaAfterMatchArm { pos, .. }
+      | CompileError::InvalidAssignmentTarget { pos, .. }
+      | CompileError::EmptyUseTree { pos, .. }
+      | CompileError::AliasOnUseGroup { pos, .. }
+      | CompileError::DuplicateEnumVariant { pos, .. }
+      | CompileError::BreakOutsideLoop { pos, .. }
+      | CompileError::ContinueOutsideLoop { pos, .. }
+      | CompileError::ReturnOutsideFunction { pos, .. }
+      | CompileError::ExpectedToken { pos, .. } => *pos,
+    }
+  }
+
+  /// The stable code of the error, which is used by `--error-format=json` and `--explain`.
+  /// Codes are never reused or renumbered, a new error takes the next unused code.
+  pub fn get_code(&self) -> &'static str {
+    match self {
+      CompileError::ImbalancedPair { .. } => "E0001",
+      CompileError::InvalidFormatNumber { .. } => "E0002",
+      CompileError::InvalidEmptyChar { .. } => "E0003",
+      CompileError::UnclosedCharLiteral { .. } => "E0004",
+      CompileError::UnexpectedToken { .. } => "E0005",
+      CompileError::ExpectedExpressionAfterLeftParenthesis { .. } => "E0006",
+      CompileError::ExpectedRightParenthesisAfterExpression { .. } => "E0007",
+      CompileError::ExpectedCommaOrRightBracketAfterExpression { .. } => "E0008",
+      CompileError::ExpectedIdentifierAfterDoubleColon { .. } => "E0009",
+      CompileError::ExpectedExpressionAfterOperator { .. } => "E0010",
+      CompileError::ExpectedCommaOrRightParenthesisAfterArgument { .. } => "E0011",
+      CompileError::ExpectedIdentifierAfterDot { .. } => "E0012",
+      CompileError::ExpectedExpressionAfterLeftBracket { .. } => "E0013",
+      CompileError::ExpectedRightBracketAfterIndex { .. } => "E0014",
+      CompileError::ParamAfterRestParam { .. } => "E0015",
+      CompileError::EmptyParenthesesInFunctionDeclaration { .. } => "E0016",
+      CompileError::ConstDeclarationWithoutInitializer { .. } => "E0017",
+      CompileError::DestructDeclarationWithoutInitializer { .. } => "E0018",
+      CompileError::ExpectedFatArrowAfterMatchPattern { .. } => "E0019",
+      CompileError::ExpectedCommaAfterMatchArm { .. } => "E0020",
+      CompileError::InvalidAssignmentTarget { .. } => "E0021",
+      CompileError::EmptyUseTree { .. } => "E0022",
+      CompileError::AliasOnUseGroup { .. } => "E0023",
+      CompileError::DuplicateEnumVariant { .. } => "E0024",
+      CompileError::BreakOutsideLoop { .. } => "E0025",
+      CompileError::ContinueOutsideLoop { .. } => "E0026",
+      CompileError::ReturnOutsideFunction { .. } => "E0027",
+      CompileError::ExpectedToken { .. } => "E0028",
+      CompileError::UnclosedBlockComment { .. } => "E0029",
+    }
+  }
+
+  /// The error message without the category prefix and the trailing position,
+  /// e.g. `Invalid empty char` for `(Syntax) Invalid empty char at line 1:3`.
+  pub fn get_message(&self) -> String {
+    let full_message = self.to_string();
+    let message = full_message
+      .split_once(") ")
+      .map_or(full_message.as_str(), |(_, message)| message);
+    let pos_suffix = format!(" at {}", self.get_pos());
+    message
+      .strip_suffix(pos_suffix.as_str())
+      .unwrap_or(message)
+      .to_string()
+  }
+}
+
+/// The long-form explanation of an error code for `--explain`,
+/// with an erroneous and a corrected code example.
+pub fn get_explanation(code: &str) -> Option<&'static str> {
+  match code {
+    "E0001" => Some(include_str!("explanations/E0001.md")),
+    "E0002" => Some(include_str!("explanations/E0002.md")),
+    "E0003" => Some(include_str!("explanations/E0003.md")),
+    "E0004" => Some(include_str!("explanations/E0004.md")),
+    "E0005" => Some(include_str!("explanations/E0005.md")),
+    "E0006" => Some(include_str!("explanations/E0006.md")),
+    "E0007" => Some(include_str!("explanations/E0007.md")),
+    "E0008" => Some(include_str!("explanations/E0008.md")),
+    "E0009" => Some(include_str!("explanations/E0009.md")),
+    "E0010" => Some(include_str!("explanations/E0010.md")),
+    "E0011" => Some(include_str!("explanations/E0011.md")),
+    "E0012" => Some(include_str!("explanations/E0012.md")),
+    "E0013" => Some(include_str!("explanations/E0013.md")),
+    "E0014" => Some(include_str!("explanations/E0014.md")),
+    "E0015" => Some(include_str!("explanations/E0015.md")),
+    "E0016" => Some(include_str!("explanations/E0016.md")),
+    "E0017" => Some(include_str!("explanations/E0017.md")),
+    "E0018" => Some(include_str!("explanations/E0018.md")),
+    "E0019" => Some(include_str!("explanations/E0019.md")),
+    "E0020" => Some(include_str!("explanations/E0020.md")),
+    "E0021" => Some(include_str!("explanations/E0021.md")),
+    "E0022" => Some(include_str!("explanations/E0022.md")),
+    "E0023" => Some(include_str!("explanations/E0023.md")),
+    "E0024" => Some(include_str!("explanations/E0024.md")),
+    "E0025" => Some(include_str!("explanations/E0025.md")),
+    "E0026" => Some(include_str!("explanations/E0026.md")),
+    "E0027" => Some(include_str!("explanations/E0027.md")),
+    "E0028" => Some(include_str!("explanations/E0028.md")),
+    "E0029" => Some(include_str!("explanations/E0029.md")),
+    _ => None,
+  }
 }
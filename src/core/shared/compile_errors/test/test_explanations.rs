@@ -0,0 +1,50 @@
+#[cfg(test)]
+fn get_code_examples(explanation: &str) -> Vec<String> {
+  explanation
+    .split("```nebula\n")
+    .skip(1)
+    .filter_map(|block| block.split_once("```").map(|(code, _)| code.to_string()))
+    .collect()
+}
+
+#[test]
+fn test_every_error_code_has_explanation() {
+  use crate::core::{parser::impls::Parser, shared::compile_errors::get_explanation};
+  for number in 1..=29 {
+    let code = format!("E{:04}", number);
+    let explanation = get_explanation(&code).expect(&code);
+    let examples = get_code_examples(explanation);
+    // an error code which is not emitted has no erroneous example
+    let is_emitted = !explanation.contains("this error code is not emitted");
+    assert_eq!(
+      examples.len(),
+      if is_emitted { 2 } else { 1 },
+      "{} should have {} examples",
+      code,
+      if is_emitted { "two" } else { "one" }
+    );
+
+    // the erroneous example must report the explained error
+    if is_emitted {
+      let mut parser = Parser::new(&examples[0]);
+      let (_, errors) = parser.parse_program();
+      assert!(
+        errors.iter().any(|error| error.get_code() == code),
+        "the erroneous example of {} reports: {:?}",
+        code,
+        errors
+      );
+    }
+
+    // and the corrected one must be error-free
+    let mut parser = Parser::new(examples.last().unwrap());
+    let (_, errors) = parser.parse_program();
+    assert!(
+      errors.is_empty(),
+      "the corrected example of {} reports: {:?}",
+      code,
+      errors
+    );
+  }
+  assert!(get_explanation("E0030").is_none());
+}
@@ -0,0 +1,395 @@
+use std::fmt::{Display, Formatter};
+
+use colored::{ColoredString, Colorize};
+
+use super::compile_errors::CompileError;
+use super::source_map::{SourceMap, SpanLocation};
+use super::span::{FileId, Span};
+
+mod test;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+  pub span: Span,
+  pub message: String,
+}
+impl Label {
+  pub fn new(span: Span, message: &str) -> Label {
+    Label {
+      span,
+      message: message.to_string(),
+    }
+  }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+  Error,
+  Warning,
+  // attached to another diagnostic as extra information
+  Note,
+}
+impl Severity {
+  fn paint(&self, text: &str) -> ColoredString {
+    match self {
+      Severity::Error => text.red().bold(),
+      Severity::Warning => text.yellow().bold(),
+      Severity::Note => text.bold(),
+    }
+  }
+}
+impl Display for Severity {
+  fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{}",
+      match self {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Note => "note",
+      }
+    )
+  }
+}
+
+/// A rustc-style report of a compile error or a lint: the message, a primary label
+/// underlining where it occurs, secondary labels on related places, notes and help.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+  pub code: &'static str,
+  pub severity: Severity,
+  pub message: String,
+  pub primary: Label,
+  pub secondary: Vec<Label>,
+  pub notes: Vec<String>,
+  pub help: Option<String>,
+}
+
+impl Diagnostic {
+  /// The compile error is reported in the file it's found, whose id is given by the caller
+  /// because the positions of compile errors don't record it.
+  pub fn from_compile_error(error: &CompileError, file_id: FileId) -> Diagnostic {
+    let to_span = |pos| Span::from_pos(file_id, pos);
+    let mut secondary: Vec<Label> = vec![];
+    let (primary_message, help) = match error {
+      CompileError::ImbalancedPair {
+        punc_str, open_pos, ..
+      } => {
+        if let Some(open_pos) = open_pos {
+          secondary.push(Label::new(
+            to_span(*open_pos),
+            "the nearest unclosed delimiter",
+          ));
+        }
+        if ["(", "{", "["].contains(&punc_str.as_str()) {
+          (
+            String::from("unclosed delimiter"),
+            Some(String::from("add a matching closing delimiter")),
+          )
+        } else {
+          (
+            String::from("unexpected closing delimiter"),
+            Some(String::from(
+              "remove this delimiter or add a matching opening one before it",
+            )),
+          )
+        }
+      }
+      CompileError::InvalidFormatNumber { .. } => (
+        String::from("invalid number literal"),
+        Some(String::from(
+          "digits are required after the prefix or symbol, e.g. `0x1F`, `0b101`, `1.5`, `1e3`",
+        )),
+      ),
+      CompileError::InvalidEmptyChar { .. } => (
+        String::from("empty char literal"),
+        Some(String::from(
+          "a char literal must contain exactly one character, use a string `\"\"` for empty text",
+        )),
+      ),
+      CompileError::UnclosedCharLiteral { .. } => (
+        String::from("unclosed char literal"),
+        Some(String::from(
+          "close the char literal with a single quote `'`",
+        )),
+      ),
+      CompileError::UnclosedBlockComment { .. } => (
+        String::from("unclosed block comment"),
+        Some(String::from("close the comment with `*/`")),
+      ),
+      CompileError::ExpectedRightParenthesisAfterExpression { .. } => (
+        String::from("expected `)`"),
+        Some(String::from(
+          "add `)` to close the parenthesized expression",
+        )),
+      ),
+      CompileError::ParamAfterRestParam { .. } => (
+        String::from("parameter after the rest parameter"),
+        Some(String::from("move the rest parameter to the end")),
+      ),
+      CompileError::EmptyParenthesesInFunctionDeclaration { .. } => (
+        String::from("empty parentheses"),
+        Some(String::from("remove the parentheses `()`")),
+      ),
+      CompileError::ConstDeclarationWithoutInitializer { .. } => (
+        String::from("missing initializer"),
+        Some(String::from(
+          "initialize it with `=`, or declare it with `var` instead",
+        )),
+      ),
+      CompileError::DestructDeclarationWithoutInitializer { .. } => (
+        String::from("missing initializer"),
+        Some(String::from("initialize it with `=` and an array value")),
+      ),
+      CompileError::ExpectedFatArrowAfterMatchPattern { .. } => (
+        String::from("expected `=>`"),
+        Some(String::from(
+          "add `=>` between the pattern and the arm body",
+        )),
+      ),
+      CompileError::ExpectedCommaAfterMatchArm { .. } => (
+        String::from("expected `,`"),
+        Some(String::from(
+          "add `,` after the arm body, or wrap the body with a block",
+        )),
+      ),
+      CompileError::InvalidAssignmentTarget { .. } => (
+        String::from("can not be assigned"),
+        Some(String::from(
+          "only an identifier, a field access or an index access can be assigned",
+        )),
+      ),
+      CompileError::EmptyUseTree { .. } => (
+        String::from("empty use tree"),
+        Some(String::from("remove the empty braces `{}`")),
+      ),
+      CompileError::AliasOnUseGroup { .. } => (
+        String::from("alias on a group"),
+        Some(String::from("alias the items inside the group instead")),
+      ),
+      CompileError::DuplicateEnumVariant { first_pos, .. } => {
+        secondary.push(Label::new(to_span(*first_pos), "first defined here"));
+        (
+          String::from("defined again here"),
+          Some(String::from("rename or remove one of the variants")),
+        )
+      }
+      CompileError::BreakOutsideLoop { .. } | CompileError::ContinueOutsideLoop { .. } => (
+        String::from("outside of a loop"),
+        Some(String::from(
+          "it can only be used in the body of `loop`, `while` or `for`",
+        )),
+      ),
+      CompileError::ReturnOutsideFunction { .. } => (
+        String::from("outside of a function"),
+        Some(String::from(
+          "it can only be used in a function body or a lambda body",
+        )),
+      ),
+      CompileError::ExpectedToken { expected, .. } => (format!("expected {}", expected), None),
+      _ => (String::new(), None),
+    };
+    Diagnostic {
+      code: error.get_code(),
+      severity: Severity::Error,
+      message: error.get_message(),
+      primary: Label {
+        span: to_span(error.get_pos()),
+        message: primary_message,
+      },
+      secondary,
+      notes: vec![],
+      help,
+    }
+  }
+
+  /// Renders the diagnostic with the source snippets, e.g.
+  /// ```text
+  /// error[E0028]: Expected semicolon ';' after variable declaration but found "var"
+  ///  --> main.n:3:3
+  ///   |
+  /// 3 |   var b = 2;
+  ///   |   ^^^ expected semicolon ';' after variable declaration
+  /// ```
+  /// The labels in other files are grouped after the file of the primary label,
+  /// each file is headed by `::: file:line:col`.
+  /// Colours are applied by `colored`, which is switched by `--color`.
+  pub fn render(&self, source_map: &SourceMap) -> String {
+    let primary_file_id = self.primary.span.file_id;
+
+    // (location, label, is primary)
+    let mut located: Vec<(SpanLocation, &Label, bool)> = vec![];
+    for (label, is_primary) in std::iter::once((&self.primary, true))
+      .chain(self.secondary.iter().map(|label| (label, false)))
+    {
+      if let Some(location) = source_map.resolve(label.span) {
+        located.push((location, label, is_primary));
+      }
+    }
+    // stable sort keeps the primary label in front of others on the same line
+    located.sort_by_key(|(location, label, _)| {
+      let file_id = label.span.file_id;
+      (file_id != primary_file_id, file_id, location.start_line)
+    });
+
+    let max_line = located
+      .iter()
+      .map(|(location, ..)| location.start_line)
+      .max()
+      .unwrap_or(1);
+    let gutter_width = max_line.to_string().len();
+    let gutter = " ".repeat(gutter_width);
+    let bar = "|".blue().bold();
+    let locate = |location: &SpanLocation| {
+      format!(
+        "{}:{}:{}",
+        location.file_name, location.start_line, location.start_col
+      )
+    };
+
+    let mut rendered = vec![format!(
+      "{}: {}",
+      self
+        .severity
+        .paint(&format!("{}[{}]", self.severity, self.code)),
+      self.message.bold()
+    )];
+    if let Some(location) = source_map.resolve(self.primary.span) {
+      rendered.push(format!(
+        "{}{} {}",
+        gutter,
+        "-->".blue().bold(),
+        locate(&location)
+      ));
+      rendered.push(format!("{} {}", gutter, bar));
+    }
+    let mut last_line: Option<(FileId, usize)> = None;
+    for (location, label, is_primary) in located {
+      let file_id = label.span.file_id;
+      let line = location.start_line;
+      if last_line.map(|(last_file_id, _)| last_file_id) != Some(file_id)
+        && file_id != primary_file_id
+      {
+        if last_line.is_some() {
+          rendered.push(format!("{} {}", gutter, bar));
+        }
+        rendered.push(format!(
+          "{}{} {}",
+          gutter,
+          ":::".blue().bold(),
+          locate(&location)
+        ));
+        rendered.push(format!("{} {}", gutter, bar));
+      }
+      if last_line != Some((file_id, line)) {
+        if last_line
+          .is_some_and(|(last_file_id, last_line)| last_file_id == file_id && line > last_line + 1)
+        {
+          rendered.push("...".blue().bold().to_string());
+        }
+        rendered.push(format!(
+          "{} {} {}",
+          format!("{:>width$}", line, width = gutter_width)
+            .blue()
+            .bold(),
+          bar,
+          location.line_text
+        ));
+        last_line = Some((file_id, line));
+      }
+
+      // underlines the span in this line only, even if it crosses lines
+      let padding = location.start_col - 1;
+      let line_end_col = location.line_text.chars().count() + 1;
+      let end_col = if location.end_line == line {
+        location.end_col.min(line_end_col)
+      } else {
+        line_end_col
+      };
+      let width = end_col.saturating_sub(location.start_col).max(1);
+      let marker = if is_primary { "^" } else { "-" };
+      let underline = format!("{} {}", marker.repeat(width), label.message)
+        .trim_end()
+        .to_string();
+      rendered.push(format!(
+        "{} {} {}{}",
+        gutter,
+        bar,
+        " ".repeat(padding),
+        if is_primary {
+          self.severity.paint(&underline)
+        } else {
+          underline.blue().bold()
+        }
+      ));
+    }
+    if !self.notes.is_empty() || self.help.is_some() {
+      rendered.push(format!("{} {}", gutter, bar));
+    }
+    for note in self.notes.iter() {
+      rendered.push(format!(
+        "{} {} {}: {}",
+        gutter,
+        "=".blue().bold(),
+        Severity::Note.paint(&Severity::Note.to_string()),
+        note
+      ));
+    }
+    if let Some(help) = &self.help {
+      rendered.push(format!(
+        "{} {} {}: {}",
+        gutter,
+        "=".blue().bold(),
+        "help".bold(),
+        help
+      ));
+    }
+    rendered.join("\n")
+  }
+
+  /// Serializes the diagnostic as a single-line JSON object for editors and CI, e.g.
+  /// ```text
+  /// {"code":"E0003","severity":"error","message":"Invalid empty char","file":"main.n",
+  ///  "start":{"line":1,"column":10},"end":{"line":1,"column":11}}
+  /// ```
+  /// Lines and columns are 1-based, and the end column points right after the span.
+  pub fn to_json(&self, source_map: &SourceMap) -> String {
+    let (file_name, (start_line, start_col), (end_line, end_col)) =
+      match source_map.resolve(self.primary.span) {
+        Some(location) => (
+          location.file_name,
+          (location.start_line, location.start_col),
+          (location.end_line, location.end_col),
+        ),
+        None => ("", (0, 0), (0, 0)),
+      };
+    format!(
+      "{{\"code\":\"{}\",\"severity\":\"{}\",\"message\":\"{}\",\"file\":\"{}\",\
+      \"start\":{{\"line\":{},\"column\":{}}},\"end\":{{\"line\":{},\"column\":{}}}}}",
+      self.code,
+      self.severity,
+      escape_json(&self.message),
+      escape_json(file_name),
+      start_line,
+      start_col,
+      end_line,
+      end_col
+    )
+  }
+}
+
+pub fn escape_json(raw: &str) -> String {
+  let mut escaped = String::with_capacity(raw.len());
+  for c in raw.chars() {
+    match c {
+      '"' => escaped.push_str("\\\""),
+      '\\' => escaped.push_str("\\\\"),
+      '\n' => escaped.push_str("\\n"),
+      '\r' => escaped.push_str("\\r"),
+      '\t' => escaped.push_str("\\t"),
+      c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+      c => escaped.push(c),
+    }
+  }
+  escaped
+}
@@ -0,0 +1,2 @@
+mod test_json;
+mod test_render;
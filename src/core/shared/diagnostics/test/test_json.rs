@@ -0,0 +1,49 @@
+#[test]
+fn test_diagnostics_to_json() {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::{diagnostics::Diagnostic, source_map::SourceMap},
+  };
+  let source = "fn main {\n  var n = 0b;\n  var c = 1\n}\n";
+  let mut source_map = SourceMap::new();
+  let file_id = source_map.add_file("src/main.n", source.to_string());
+  let mut parser = Parser::new_in_file(source, file_id);
+  let (_, errors) = parser.parse_program();
+  let json_lines: Vec<String> = errors
+    .iter()
+    .map(|error| Diagnostic::from_compile_error(error, file_id).to_json(&source_map))
+    .collect();
+  assert_eq!(
+    json_lines,
+    vec![
+      r#"{"code":"E0002","severity":"error","message":"Invalid binary number format","file":"src/main.n","start":{"line":2,"column":11},"end":{"line":2,"column":13}}"#,
+      r#"{"code":"E0028","severity":"error","message":"Expected semicolon ';' after variable declaration but found \"}\"","file":"src/main.n","start":{"line":4,"column":1},"end":{"line":4,"column":2}}"#,
+    ]
+  );
+}
+
+#[test]
+fn test_error_codes_are_stable() {
+  use crate::core::shared::{ast::Position, compile_errors::CompileError};
+  let pos = Position::new(1, 2, 0, 1);
+  assert_eq!(
+    CompileError::ImbalancedPair {
+      punc_str: String::from(")"),
+      pos,
+      open_pos: None,
+    }
+    .get_code(),
+    "E0001"
+  );
+  assert_eq!(CompileError::InvalidEmptyChar { pos }.get_code(), "E0003");
+  assert_eq!(CompileError::BreakOutsideLoop { pos }.get_code(), "E0025");
+  assert_eq!(
+    CompileError::ExpectedToken {
+      expected: String::from("semicolon ';'"),
+      found: String::from("}"),
+      pos,
+    }
+    .get_code(),
+    "E0028"
+  );
+}
@@ -0,0 +1,139 @@
+#[cfg(test)]
+fn render_all_errors(source: &str) -> Vec<String> {
+  use crate::core::{
+    parser::impls::Parser,
+    shared::{diagnostics::Diagnostic, source_map::SourceMap},
+  };
+  colored::control::set_override(false);
+  let mut source_map = SourceMap::new();
+  let file_id = source_map.add_file("main.n", source.to_string());
+  let mut parser = Parser::new_in_file(source, file_id);
+  let (_, errors) = parser.parse_program();
+  errors
+    .iter()
+    .map(|error| Diagnostic::from_compile_error(error, file_id).render(&source_map))
+    .collect()
+}
+
+#[test]
+fn test_render_primary_label_under_token() {
+  let rendered = render_all_errors("fn main {\n  var a = 1\n  var b = 2;\n}\n");
+  assert_eq!(
+    rendered,
+    vec![[
+      r#"error[E0028]: Expected semicolon ';' after variable declaration but found "var""#,
+      " --> main.n:3:3",
+      "  |",
+      "3 |   var b = 2;",
+      "  |   ^^^ expected semicolon ';' after variable declaration",
+    ]
+    .join("\n")]
+  );
+}
+
+#[test]
+fn test_render_secondary_labels_and_help() {
+  let rendered = render_all_errors("fn main {\n  var s = a[1);\n}\n");
+  // the errors are in source order, the unclosed '[' comes first
+  assert!(rendered[0].starts_with(r#"error[E0001]: Punctuation "[" is mismatched"#));
+  assert_eq!(
+    rendered[1],
+    [
+      r#"error[E0001]: Punctuation ")" is mismatched"#,
+      " --> main.n:2:14",
+      "  |",
+      "2 |   var s = a[1);",
+      "  |              ^ unexpected closing delimiter",
+      "  |            - the nearest unclosed delimiter",
+      "  |",
+      "  = help: remove this delimiter or add a matching opening one before it",
+    ]
+    .join("\n")
+  );
+
+  let rendered = render_all_errors("enum Color {\n  Red,\n  Green,\n  Red,\n}\n");
+  assert_eq!(
+    rendered,
+    vec![[
+      r#"error[E0024]: Duplicate enum variant "Red""#,
+      " --> main.n:4:3",
+      "  |",
+      "2 |   Red,",
+      "  |   --- first defined here",
+      "...",
+      "4 |   Red,",
+      "  |   ^^^ defined again here",
+      "  |",
+      "  = help: rename or remove one of the variants",
+    ]
+    .join("\n")]
+  );
+}
+
+#[test]
+fn test_render_lexer_errors() {
+  let rendered = render_all_errors("fn main {\n  var n = 0x;\n}\n");
+  assert_eq!(
+    rendered[0],
+    [
+      "error[E0002]: Invalid hexadecimal number format",
+      " --> main.n:2:11",
+      "  |",
+      "2 |   var n = 0x;",
+      "  |           ^^ invalid number literal",
+      "  |",
+      "  = help: digits are required after the prefix or symbol, e.g. `0x1F`, `0b101`, `1.5`, `1e3`",
+    ]
+    .join("\n")
+  );
+}
+
+#[test]
+fn test_render_labels_in_other_files() {
+  use crate::core::shared::{
+    diagnostics::{Diagnostic, Label, Severity},
+    source_map::SourceMap,
+    span::Span,
+  };
+  colored::control::set_override(false);
+  let mut source_map = SourceMap::new();
+  let main_id = source_map.add_file("main.n", String::from("fn main {\n  greet(1);\n}\n"));
+  let lib_id = source_map.add_file("lib.n", String::from("\npub fn greet {\n}\n"));
+  let diagnostic = Diagnostic {
+    code: "E9999",
+    severity: Severity::Error,
+    message: String::from("too many arguments"),
+    primary: Label::new(Span::new(main_id, 18, 19), "unexpected argument"),
+    secondary: vec![Label::new(
+      Span::new(lib_id, 8, 13),
+      "defined without params",
+    )],
+    notes: vec![],
+    help: None,
+  };
+  assert_eq!(
+    diagnostic.render(&source_map),
+    [
+      "error[E9999]: too many arguments",
+      " --> main.n:2:9",
+      "  |",
+      "2 |   greet(1);",
+      "  |         ^ unexpected argument",
+      "  |",
+      " ::: lib.n:2:8",
+      "  |",
+      "2 | pub fn greet {",
+      "  |        ----- defined without params",
+    ]
+    .join("\n")
+  );
+}
+
+#[test]
+fn test_parse_color_choice() {
+  use crate::utils::log::ColorChoice;
+  assert_eq!(ColorChoice::from_arg("auto"), Some(ColorChoice::Auto));
+  assert_eq!(ColorChoice::from_arg("always"), Some(ColorChoice::Always));
+  assert_eq!(ColorChoice::from_arg("never"), Some(ColorChoice::Never));
+  assert_eq!(ColorChoice::from_arg("sometimes"), None);
+}
@@ -1,5 +1,8 @@
 pub mod ast;
 pub mod compile_errors;
+pub mod diagnostics;
+pub mod source_map;
+pub mod span;
 
 pub fn nebula_interal_err(str: &str) -> String {
   return format!("[Nebula Internal Error] {}", str);
@@ -0,0 +1,97 @@
+use std::path::Path;
+
+use super::span::{FileId, LineIndex, Span};
+
+mod test;
+
+/// A loaded source file, its line starts are computed once when it's added.
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+  // the name shown in diagnostics, such as the path given on the command line
+  name: String,
+  source: String,
+  line_index: LineIndex,
+}
+impl SourceFile {
+  pub fn get_source(&self) -> &str {
+    &self.source
+  }
+
+  /// The line and column (both 1-based) of the byte offset.
+  pub fn get_line_col(&self, offset: usize) -> (usize, usize) {
+    self.line_index.get_line_col(&self.source, offset)
+  }
+
+  /// The content of the line (1-based), without the line break.
+  pub fn get_line_text(&self, line: usize) -> &str {
+    self.line_index.get_line_text(&self.source, line)
+  }
+}
+
+/// Where a span is in human readable form, all lines and columns are 1-based,
+/// and the end points right after the span.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpanLocation<'a> {
+  pub file_name: &'a str,
+  pub start_line: usize,
+  pub start_col: usize,
+  pub end_line: usize,
+  pub end_col: usize,
+  // the source text covered by the span
+  pub excerpt: &'a str,
+  // the whole line where the span starts, without the line break
+  pub line_text: &'a str,
+}
+
+/// Owns all source files of a compilation, a file is identified by the `FileId`
+/// given when it's added, which is its index in the map.
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+  files: Vec<SourceFile>,
+}
+impl SourceMap {
+  pub fn new() -> SourceMap {
+    SourceMap { files: vec![] }
+  }
+
+  /// Adds a source, e.g. a file read from disk or a source given by tests.
+  pub fn add_file(&mut self, name: &str, source: String) -> FileId {
+    let id = FileId(self.files.len());
+    self.files.push(SourceFile {
+      name: name.to_string(),
+      line_index: LineIndex::new(&source),
+      source,
+    });
+    id
+  }
+
+  /// Reads the file from disk, the name is shown in diagnostics instead of the path.
+  pub fn load_file(&mut self, name: &str, path: &Path) -> Result<FileId, String> {
+    match std::fs::read_to_string(path) {
+      Ok(source) => Ok(self.add_file(name, source)),
+      Err(why) => Err(format!("couldn't read {} ({})", path.display(), why)),
+    }
+  }
+
+  pub fn get_file(&self, file_id: FileId) -> Option<&SourceFile> {
+    self.files.get(file_id.0)
+  }
+
+  /// Resolves the span to its file name, lines, columns and excerpt,
+  /// `None` if the span is out of its file.
+  pub fn resolve(&self, span: Span) -> Option<SpanLocation<'_>> {
+    let file = self.get_file(span.file_id)?;
+    let excerpt = file.source.get(span.start..span.end)?;
+    let (start_line, start_col) = file.get_line_col(span.start);
+    let (end_line, end_col) = file.get_line_col(span.end);
+    Some(SpanLocation {
+      file_name: &file.name,
+      start_line,
+      start_col,
+      end_line,
+      end_col,
+      excerpt,
+      line_text: file.get_line_text(start_line),
+    })
+  }
+}
@@ -0,0 +1,72 @@
+#[test]
+fn test_source_map_assigns_file_ids() {
+  use crate::core::shared::{
+    source_map::SourceMap,
+    span::{FileId, Span},
+  };
+  let mut source_map = SourceMap::new();
+  let main_id = source_map.add_file("main.n", String::from("fn main { }"));
+  let lib_id = source_map.add_file("lib.n", String::from("pub fn f { }"));
+  assert_eq!(main_id, FileId(0));
+  assert_eq!(lib_id, FileId(1));
+  let lib_file = source_map.get_file(lib_id).unwrap();
+  assert_eq!(lib_file.get_source(), "pub fn f { }");
+  let lib_location = source_map.resolve(Span::new(lib_id, 0, 3)).unwrap();
+  assert_eq!(lib_location.file_name, "lib.n");
+  assert!(source_map.get_file(FileId(2)).is_none());
+}
+
+#[test]
+fn test_source_map_resolve_span() {
+  use crate::core::shared::{
+    source_map::{SourceMap, SpanLocation},
+    span::{FileId, Span},
+  };
+  let mut source_map = SourceMap::new();
+  source_map.add_file("main.n", String::from("fn main { }"));
+  let file_id = source_map.add_file("lib.n", String::from("use std;\nconst 名 = \"星云\";\n"));
+  let string_start = "use std;\nconst 名 = ".len();
+  let span = Span::new(file_id, string_start, string_start + "\"星云\"".len());
+  assert_eq!(
+    source_map.resolve(span),
+    Some(SpanLocation {
+      file_name: "lib.n",
+      start_line: 2,
+      start_col: 11,
+      end_line: 2,
+      end_col: 15,
+      excerpt: "\"星云\"",
+      line_text: "const 名 = \"星云\";",
+    })
+  );
+  assert_eq!(
+    source_map.get_file(file_id).unwrap().get_line_text(2),
+    "const 名 = \"星云\";"
+  );
+  // out of the file, or in an unknown file
+  assert_eq!(source_map.resolve(Span::new(file_id, 0, 100)), None);
+  assert_eq!(source_map.resolve(Span::new(FileId(5), 0, 1)), None);
+}
+
+#[test]
+fn test_source_map_load_file() {
+  use crate::core::shared::{source_map::SourceMap, span::Span};
+  use std::path::Path;
+  let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/src/fibonacci.n");
+  let mut source_map = SourceMap::new();
+  let file_id = source_map.load_file("fibonacci.n", &path).unwrap();
+  let file = source_map.get_file(file_id).unwrap();
+  assert_eq!(
+    source_map
+      .resolve(Span::new(file_id, 0, 0))
+      .map(|location| location.file_name),
+    Some("fibonacci.n")
+  );
+  assert_eq!(
+    file.get_source(),
+    include_str!("../../../../../examples/src/fibonacci.n")
+  );
+  assert!(source_map
+    .load_file("missing.n", &path.with_file_name("missing.n"))
+    .is_err());
+}
@@ -0,0 +1,95 @@
+use super::ast::Position;
+
+mod test;
+
+/// Identifies a source file in a compilation, the file being compiled alone is `FileId(0)`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(pub usize);
+
+/// The byte range `start..end` of a node or a token in its source file.
+/// The line and column are not stored, they're resolved on demand by a `LineIndex`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Span {
+  pub file_id: FileId,
+  pub start: usize,
+  pub end: usize,
+}
+impl Span {
+  pub fn new(file_id: FileId, start: usize, end: usize) -> Span {
+    Span {
+      file_id,
+      start,
+      end,
+    }
+  }
+
+  /// The span of a lexed token or a compile error.
+  pub fn from_pos(file_id: FileId, pos: Position) -> Span {
+    Span::new(file_id, pos.start, pos.end)
+  }
+
+  /// The smallest span covering both spans, which must be in the same file.
+  pub fn to(&self, other: Span) -> Span {
+    Span::new(
+      self.file_id,
+      self.start.min(other.start),
+      self.end.max(other.end),
+    )
+  }
+}
+
+/// The byte offsets where the lines of a source start, maps byte offsets to
+/// human readable lines and columns.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex {
+  line_starts: Vec<usize>,
+}
+impl LineIndex {
+  pub fn new(source: &str) -> LineIndex {
+    let mut line_starts = vec![0];
+    line_starts.extend(
+      source
+        .bytes()
+        .enumerate()
+        .filter(|(_, byte)| *byte == b'\n')
+        .map(|(offset, _)| offset + 1),
+    );
+    LineIndex { line_starts }
+  }
+
+  /// The byte offset where the line (1-based) starts.
+  pub fn get_line_start(&self, line: usize) -> Option<usize> {
+    self.line_starts.get(line.checked_sub(1)?).copied()
+  }
+
+  /// The line (1-based) which contains the byte offset.
+  pub fn get_line(&self, offset: usize) -> usize {
+    self
+      .line_starts
+      .partition_point(|line_start| *line_start <= offset)
+  }
+
+  /// Resolves the byte offset in the indexed source to the line and column, both 1-based.
+  /// Columns are counted in chars, an offset inside a char is moved back to the char start.
+  pub fn get_line_col(&self, source: &str, offset: usize) -> (usize, usize) {
+    let mut offset = offset.min(source.len());
+    while !source.is_char_boundary(offset) {
+      offset -= 1;
+    }
+    let line = self.get_line(offset);
+    let line_start = self.line_starts[line - 1];
+    (line, source[line_start..offset].chars().count() + 1)
+  }
+
+  /// The content of the line (1-based), without the line break.
+  pub fn get_line_text<'a>(&self, source: &'a str, line: usize) -> &'a str {
+    let Some(line_start) = self.get_line_start(line) else {
+      return "";
+    };
+    let line_end = self
+      .get_line_start(line + 1)
+      .map_or(source.len(), |next_start| next_start - 1);
+    let text = &source[line_start..line_end];
+    text.strip_suffix('\r').unwrap_or(text)
+  }
+}
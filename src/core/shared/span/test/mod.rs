@@ -0,0 +1 @@
+mod test_line_index;
@@ -0,0 +1,34 @@
+#[test]
+fn test_line_index_line_col() {
+  use crate::core::shared::span::LineIndex;
+  let source = "fn main {\r\n  var 名字 = \"星云\";\n}\n";
+  let line_index = LineIndex::new(source);
+  assert_eq!(line_index.get_line_start(4), Some(source.len()));
+  assert_eq!(line_index.get_line_start(5), None);
+  assert_eq!(line_index.get_line_col(source, 0), (1, 1));
+  assert_eq!(line_index.get_line_col(source, 3), (1, 4));
+  // the line break belongs to the line it ends
+  assert_eq!(line_index.get_line_col(source, 10), (1, 11));
+  assert_eq!(line_index.get_line_col(source, 11), (2, 1));
+  // columns are counted in chars rather than bytes
+  let name_start = source.find("名字").unwrap();
+  let equal_start = source.find('=').unwrap();
+  assert_eq!(line_index.get_line_col(source, name_start), (2, 7));
+  assert_eq!(line_index.get_line_col(source, equal_start), (2, 10));
+  // an offset inside a char is moved back to the char start
+  assert_eq!(line_index.get_line_col(source, name_start + 1), (2, 7));
+  assert_eq!(line_index.get_line_col(source, source.len()), (4, 1));
+}
+
+#[test]
+fn test_line_index_line_text() {
+  use crate::core::shared::span::LineIndex;
+  let source = "use std::io;\r\n\nfn main { }";
+  let line_index = LineIndex::new(source);
+  assert_eq!(line_index.get_line_text(source, 1), "use std::io;");
+  assert_eq!(line_index.get_line_text(source, 2), "");
+  assert_eq!(line_index.get_line_text(source, 3), "fn main { }");
+  assert_eq!(line_index.get_line_text(source, 4), "");
+  assert_eq!(line_index.get_line_start(3), Some(15));
+  assert_eq!(line_index.get_line_start(0), None);
+}
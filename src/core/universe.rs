@@ -0,0 +1,160 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The manifest file at the root of a Nebula package.
+pub const MANIFEST_NAME: &str = "universe.toml";
+
+/// Properties: table name, entries of `key = value`
+pub type ManifestTables = HashMap<String, HashMap<String, String>>;
+
+/// Finds the manifest in the directory of the file, or in any of its ancestors.
+pub fn find_manifest(file_path: &Path) -> Option<PathBuf> {
+  file_path
+    .ancestors()
+    .skip(1)
+    .map(|dir| dir.join(MANIFEST_NAME))
+    .find(|manifest_path| manifest_path.is_file())
+}
+
+fn unquote(raw: &str) -> &str {
+  raw
+    .strip_prefix('"')
+    .and_then(|raw| raw.strip_suffix('"'))
+    .unwrap_or(raw)
+}
+
+/// The table of lint levels, which is the only table that must be well-formed.
+pub const LINTS_TABLE: &str = "lints";
+
+/// The depth of brackets after the line, starting from the given depth,
+/// the brackets in strings and comments are ignored.
+fn get_bracket_depth(line: &str, mut depth: usize) -> usize {
+  let mut quote: Option<char> = None;
+  let mut chars = line.chars();
+  while let Some(char) = chars.next() {
+    match (quote, char) {
+      (Some('"'), '\\') => {
+        chars.next(); // skips the escaped character
+      }
+      (Some(opening), _) if char == opening => quote = None,
+      (Some(_), _) => {}
+      (None, '"' | '\'') => quote = Some(char),
+      (None, '[' | '{') => depth += 1,
+      (None, ']' | '}') => depth = depth.saturating_sub(1),
+      (None, '#') => break,
+      _ => {}
+    }
+  }
+  depth
+}
+
+/// Reads the tables of the manifest. Only a small part of TOML is understood for now:
+/// `[table]` headers, `key = value` entries with a string or bare value, and `#` comments.
+/// Other entries such as arrays, inline tables and multi-line strings are skipped,
+/// except in the `[lints]` table which must only contain the understood entries.
+/// The entries before any header are put in the table named `""`.
+pub fn parse_manifest(content: &str) -> Result<ManifestTables, String> {
+  let mut tables: ManifestTables = HashMap::new();
+  let mut table_name = String::new();
+  let mut lines = content.lines().enumerate();
+  while let Some((index, line)) = lines.next() {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+      continue;
+    }
+    let get_error = |message: &str| format!("{} line {}: {}", MANIFEST_NAME, index + 1, message);
+    if let Some(header) = line.strip_prefix('[') {
+      let header = header.split('#').next().unwrap_or("").trim();
+      let Some(name) = header.strip_suffix(']') else {
+        if header == LINTS_TABLE {
+          return Err(get_error("unclosed table header"));
+        }
+        // the entries of an unknown table are skipped
+        table_name = header.to_string();
+        continue;
+      };
+      table_name = name.trim().to_string();
+      tables.entry(table_name.clone()).or_default();
+      continue;
+    }
+    let is_lints_table = table_name == LINTS_TABLE;
+    let Some((key, value)) = line.split_once('=') else {
+      if is_lints_table {
+        return Err(get_error("expected an entry of `key = value`"));
+      }
+      continue;
+    };
+    let value = value.trim();
+    let value = if value.starts_with("\"\"\"") || value.starts_with("'''") {
+      if is_lints_table {
+        return Err(get_error("expected a string value"));
+      }
+      // skips the lines of a multi-line string until the closing delimiter
+      let delimiter = &value[..3];
+      if !value[3..].contains(delimiter) {
+        for (_, line) in lines.by_ref() {
+          if line.contains(delimiter) {
+            break;
+          }
+        }
+      }
+      continue;
+    } else if value.starts_with('[') || value.starts_with('{') {
+      if is_lints_table {
+        return Err(get_error("expected a string value"));
+      }
+      // skips the lines of an array or an inline table until the brackets are closed
+      let mut depth = get_bracket_depth(value, 0);
+      while depth > 0 {
+        let Some((_, line)) = lines.next() else {
+          break;
+        };
+        depth = get_bracket_depth(line, depth);
+      }
+      continue;
+    } else if let Some(quoted) = value.strip_prefix('"') {
+      // strips the comment after the value, a string value may contain '#'
+      match quoted.find('"') {
+        Some(closing) => &value[..closing + 2],
+        None if is_lints_table => return Err(get_error("unclosed string")),
+        None => continue,
+      }
+    } else {
+      value.split('#').next().unwrap_or("").trim()
+    };
+    tables
+      .entry(table_name.clone())
+      .or_default()
+      .insert(unquote(key.trim()).to_string(), unquote(value).to_string());
+  }
+  Ok(tables)
+}
+
+/// The directory of source files, relative to the manifest,
+/// which can be changed by `crateRoot` in the `[package]` table.
+pub const DEFAULT_CRATE_ROOT: &str = "src";
+
+/// The extension of Nebula source files.
+pub const SOURCE_EXTENSION: &str = "n";
+
+/// Finds all source files under the directory and its subdirectories, sorted by path.
+pub fn find_source_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+  let entries =
+    std::fs::read_dir(dir).map_err(|why| format!("couldn't read {} ({})", dir.display(), why))?;
+  let mut source_files: Vec<PathBuf> = vec![];
+  for entry in entries {
+    let path = entry
+      .map_err(|why| format!("couldn't read {} ({})", dir.display(), why))?
+      .path();
+    if path.is_dir() {
+      source_files.append(&mut find_source_files(&path)?);
+    } else if path
+      .extension()
+      .is_some_and(|extension| extension == SOURCE_EXTENSION)
+    {
+      source_files.push(path);
+    }
+  }
+  source_files.sort();
+  Ok(source_files)
+}
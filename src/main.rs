@@ -2,6 +2,6 @@ mod core;
 mod utils;
 
 #[allow(dead_code)] // During development, hide warnings for unused code
-fn main() {
-  core::entry::run();
+fn main() -> std::process::ExitCode {
+  core::entry::run()
 }
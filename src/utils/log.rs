@@ -1,10 +1,68 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use colored::Colorize;
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ColorChoice {
+  // colorize only when printing to a terminal
+  Auto,
+  Always,
+  Never,
+}
+impl ColorChoice {
+  /// Parses the value of `--color=auto|always|never`.
+  pub fn from_arg(value: &str) -> Option<ColorChoice> {
+    match value {
+      "auto" => Some(ColorChoice::Auto),
+      "always" => Some(ColorChoice::Always),
+      "never" => Some(ColorChoice::Never),
+      _ => None,
+    }
+  }
+}
+
+/// Switches the colour output of all logs and diagnostics.
+pub fn set_color_choice(choice: ColorChoice) {
+  match choice {
+    ColorChoice::Auto => {
+      if std::io::stdout().is_terminal() {
+        // still respects the `NO_COLOR`/`CLICOLOR` environment variables
+        colored::control::unset_override();
+      } else {
+        colored::control::set_override(false);
+      }
+    }
+    ColorChoice::Always => colored::control::set_override(true),
+    ColorChoice::Never => colored::control::set_override(false),
+  }
+}
+
+static INFO_ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Info logs are disabled when the output must be machine-readable.
+pub fn set_info_enabled(enabled: bool) {
+  INFO_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
 pub fn info(msg: &String) {
-  println!("{}{}", "Info: ".bold().cyan(), msg);
+  if INFO_ENABLED.load(Ordering::Relaxed) {
+    println!("{}{}", "Info: ".bold().cyan(), msg);
+  }
+}
+
+static ERROR_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+/// Error logs go to stderr when stdout must only contain machine-readable lines.
+pub fn set_error_to_stderr(enabled: bool) {
+  ERROR_TO_STDERR.store(enabled, Ordering::Relaxed);
 }
 
 pub fn error(msg: &String) {
   let red_wrapped = format!("Error: {}", msg);
-  println!("{}", red_wrapped.bold().red());
+  if ERROR_TO_STDERR.load(Ordering::Relaxed) {
+    eprintln!("{}", red_wrapped.bold().red());
+  } else {
+    println!("{}", red_wrapped.bold().red());
+  }
 }